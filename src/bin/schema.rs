@@ -1,4 +1,4 @@
-use bidding_platform::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use bidding_platform::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 use cosmwasm_schema::write_api;
 
 fn main() {
@@ -6,5 +6,6 @@ fn main() {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        sudo: SudoMsg,
     }
 }
@@ -1,36 +1,85 @@
-use cosmwasm_std::{ensure, Decimal, DepsMut, MessageInfo, Response, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Coin, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult, SubMsg, SubMsgResult, Uint128,
+};
 use cw2::set_contract_version;
 
 use crate::{
     error::ContractError,
-    msg::InstantiateMsg,
-    state::{CommissionParams, BID_DENOM, BID_OPEN, COMMISSION_PARAMS, HIGHEST_BID, ITEM, OWNER},
+    msg::{AuctionHookMsg, InstantiateMsg, IsVerifiedResponse, RegistryQueryMsg, TieBreak},
+    state::{
+        BatchEpochs, BidderEligibility, CommissionParams, ALLOWLIST, ARBITER, BATCH_EPOCHS,
+        BIDDER_HOOKS, BIDS, BID_DENOM, BID_OPEN, BLOCKLIST, BONDS, BOND_AMOUNT, CLAIM_WINDOW,
+        COMMISSION_PARAMS, ELIGIBILITY, ESCROW_TIMEOUT, GUARDIAN, HIGHEST_BID, HIGHEST_BIDDER,
+        HOOKS, ITEM, MIN_INCREMENT, OWNER, PAUSED, PROXY_BIDS, STATS, TIE_BREAK, TOTAL_BIDS,
+        TOTAL_BONDS, TOTAL_COMMISSION, TREASURY,
+    },
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const HOOK_REPLY_ID: u64 = 1;
+/// Gas a hook callback may use, running out of it fails only the hook.
+pub const HOOK_GAS_LIMIT: u64 = 500_000;
+
 pub fn instantiate(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    ensure!(
-        msg.commission_part >= Decimal::percent(0) && msg.commission_part <= Decimal::percent(25),
-        ContractError::InvalidCommissionPart
-    );
+    let commission_params = CommissionParams {
+        minimum_tokens: msg.commission_minimum_tokens,
+        part: msg.commission_part,
+        tiers: msg.commission_tiers,
+        maximum_tokens: msg.commission_maximum_tokens,
+    };
+    validate_commission(&commission_params)?;
 
     ITEM.save(deps.storage, &msg.item)?;
     BID_DENOM.save(deps.storage, &msg.bid_denom)?;
-    HIGHEST_BID.save(deps.storage, &Uint128::new(0))?;
-    COMMISSION_PARAMS.save(
-        deps.storage,
-        &CommissionParams {
-            minimum_tokens: msg.commission_minimum_tokens.clone(),
-            part: msg.commission_part.clone(),
-        },
-    )?;
+    HIGHEST_BID.save(deps.storage, &Uint128::new(0), env.block.height)?;
+    COMMISSION_PARAMS.save(deps.storage, &commission_params)?;
+    let eligibility = validate_eligibility(deps.as_ref(), msg.eligibility)?;
+    ELIGIBILITY.save(deps.storage, &eligibility)?;
+    TIE_BREAK.save(deps.storage, &msg.tie_break)?;
+    if let Some(blocks) = msg.batch_epoch_blocks {
+        ensure!(blocks > 0, ContractError::InvalidBatchEpoch);
+        let batch_epochs = BatchEpochs {
+            blocks,
+            start_height: env.block.height,
+        };
+        BATCH_EPOCHS.save(deps.storage, &batch_epochs)?;
+    }
+    if let Some(bond_amount) = msg.bond_amount {
+        ensure!(!bond_amount.is_zero(), ContractError::InvalidBondAmount);
+        BOND_AMOUNT.save(deps.storage, &bond_amount)?;
+    }
+    if let Some(arbiter) = msg.arbiter {
+        ARBITER.save(deps.storage, &deps.api.addr_validate(&arbiter)?)?;
+    }
+    if let Some(escrow_timeout) = msg.escrow_timeout {
+        ESCROW_TIMEOUT.save(deps.storage, &escrow_timeout)?;
+    }
+    if let Some(claim_window) = msg.claim_window {
+        CLAIM_WINDOW.save(deps.storage, &claim_window)?;
+    }
+    if let Some(treasury) = msg.treasury {
+        TREASURY.save(deps.storage, &deps.api.addr_validate(&treasury)?)?;
+    }
+    if let Some(guardian) = msg.guardian {
+        GUARDIAN.save(deps.storage, &deps.api.addr_validate(&guardian)?)?;
+    }
+    if let Some(min_increment) = msg.min_increment {
+        ensure!(!min_increment.is_zero(), ContractError::InvalidMinIncrement);
+        MIN_INCREMENT.save(deps.storage, &min_increment)?;
+    }
+    TOTAL_BIDS.save(deps.storage, &Uint128::zero())?;
+    TOTAL_COMMISSION.save(deps.storage, &Uint128::zero())?;
+    STATS.save(deps.storage, &Default::default())?;
+    TOTAL_BONDS.save(deps.storage, &Uint128::zero())?;
 
     BID_OPEN.save(deps.storage, &true)?;
 
@@ -49,17 +98,233 @@ pub fn instantiate(
     Ok(resp)
 }
 
+/// Hook callbacks for `msg`, to every registered hook and the hook of `bidder`. Failing hooks,
+/// out of gas included, are only reported on reply so they cannot block the auction.
+fn hook_msgs(deps: Deps, msg: AuctionHookMsg, bidder: Option<&Addr>) -> StdResult<Vec<SubMsg>> {
+    let mut hooks: Vec<Addr> = HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    if let Some(bidder) = bidder {
+        if let Some(hook) = BIDDER_HOOKS.may_load(deps.storage, bidder.clone())? {
+            if !hooks.contains(&hook) {
+                hooks.push(hook);
+            }
+        }
+    }
+
+    hooks
+        .into_iter()
+        .map(|hook| {
+            let msg = msg.clone().into_cosmos_msg(hook)?;
+            Ok(SubMsg::reply_on_error(msg, HOOK_REPLY_ID).with_gas_limit(HOOK_GAS_LIMIT))
+        })
+        .collect()
+}
+
+pub fn reply(_deps: DepsMut, reply: Reply) -> Result<Response, ContractError> {
+    match reply.id {
+        HOOK_REPLY_ID => match reply.result {
+            SubMsgResult::Err(err) => Ok(Response::new()
+                .add_attribute("action", "hook_failed")
+                .add_attribute("error", err)),
+            SubMsgResult::Ok(_) => Ok(Response::new()),
+        },
+        id => Err(ContractError::UnknownReply { id }),
+    }
+}
+
+/// Total bids and bonds refundable to everyone but the winner.
+fn unclaimed(deps: Deps) -> StdResult<Uint128> {
+    let mut unclaimed = TOTAL_BIDS.may_load(deps.storage)?.unwrap_or_default()
+        + TOTAL_BONDS.may_load(deps.storage)?.unwrap_or_default();
+
+    if let Some(winner) = HIGHEST_BIDDER.may_load(deps.storage)? {
+        let winner_bid = committed_bid(deps, &winner)?;
+        let winner_bond = BONDS.may_load(deps.storage, winner)?;
+        unclaimed -= winner_bid + winner_bond.unwrap_or_default();
+    }
+
+    Ok(unclaimed)
+}
+
+fn validate_commission(params: &CommissionParams) -> Result<(), ContractError> {
+    let max_part = Decimal::percent(25);
+    ensure!(
+        params.part <= max_part && params.tiers.iter().all(|tier| tier.part <= max_part),
+        ContractError::InvalidCommissionPart
+    );
+
+    let mut lower = Uint128::zero();
+    for tier in &params.tiers {
+        ensure!(tier.above > lower, ContractError::InvalidCommissionTiers);
+        lower = tier.above;
+    }
+
+    if let Some(maximum_tokens) = params.maximum_tokens {
+        ensure!(
+            maximum_tokens >= params.minimum_tokens,
+            ContractError::InvalidCommissionMaximum
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_eligibility(
+    deps: Deps,
+    eligibility: BidderEligibility,
+) -> StdResult<BidderEligibility> {
+    if let BidderEligibility::Registry { contract } = eligibility {
+        let contract = deps.api.addr_validate(&contract)?.into();
+        return Ok(BidderEligibility::Registry { contract });
+    }
+
+    Ok(eligibility)
+}
+
+fn is_eligible(deps: Deps, bidder: &Addr) -> StdResult<bool> {
+    match ELIGIBILITY.may_load(deps.storage)?.unwrap_or_default() {
+        BidderEligibility::Anyone => Ok(true),
+        BidderEligibility::Allowlist => Ok(ALLOWLIST.has(deps.storage, bidder.clone())),
+        BidderEligibility::Registry { contract } => {
+            let resp: IsVerifiedResponse = deps.querier.query_wasm_smart(
+                contract,
+                &RegistryQueryMsg::IsVerified {
+                    address: bidder.to_string(),
+                },
+            )?;
+            Ok(resp.verified)
+        }
+    }
+}
+
+/// Tokens `bidder` has in bids: the proxy maximum if set, the standing bid otherwise.
+fn committed_bid(deps: Deps, bidder: &Addr) -> StdResult<Uint128> {
+    match PROXY_BIDS.may_load(deps.storage, bidder.clone())? {
+        Some(max_amount) => Ok(max_amount),
+        None => Ok(BIDS
+            .may_load(deps.storage, bidder.clone())?
+            .unwrap_or_default()),
+    }
+}
+
+/// Effect a bid of `amount` tokens by `bidder` would have on the current state.
+struct BidPreview {
+    commission: Uint128,
+    total: Uint128,
+    rejection: Option<ContractError>,
+}
+
+/// Runs the commission, accumulation and highest-bid checks of a bid without mutating state.
+fn preview_bid(deps: Deps, bidder: &Addr, amount: Uint128) -> StdResult<BidPreview> {
+    let bid_denom = BID_DENOM.load(deps.storage)?;
+    let commission = COMMISSION_PARAMS.load(deps.storage)?.commission(amount)?;
+
+    let total = amount.saturating_sub(commission) + committed_bid(deps, bidder)?;
+
+    let bond_amount = BOND_AMOUNT.may_load(deps.storage)?;
+    let highest_bid_amount = HIGHEST_BID.load(deps.storage)?;
+    let rejection = if !BID_OPEN.load(deps.storage)? {
+        Some(ContractError::BidClosed)
+    } else if PAUSED.exists(deps.storage) {
+        Some(ContractError::Paused)
+    } else if bidder == OWNER.load(deps.storage)? {
+        Some(ContractError::OwnerCannotBid)
+    } else if BLOCKLIST.has(deps.storage, bidder.clone()) {
+        Some(ContractError::BidderBlocked)
+    } else if !is_eligible(deps, bidder)? {
+        Some(ContractError::BidderNotAllowed)
+    } else if bond_amount.is_some() && !BONDS.has(deps.storage, bidder.clone()) {
+        Some(ContractError::BondRequired {
+            bond: Coin {
+                amount: bond_amount.unwrap_or_default(),
+                denom: bid_denom,
+            },
+        })
+    } else if amount < commission {
+        Some(ContractError::BidRejectedBelowCommission {
+            commission: Coin {
+                amount: commission,
+                denom: bid_denom,
+            },
+        })
+    } else if total < highest_bid_amount
+        || total == highest_bid_amount
+            && (total.is_zero()
+                || TIE_BREAK.may_load(deps.storage)?.unwrap_or_default() == TieBreak::Reject)
+    {
+        Some(ContractError::BidRejected {
+            highest_bid: Coin {
+                amount: highest_bid_amount,
+                denom: bid_denom,
+            },
+        })
+    } else {
+        None
+    };
+
+    Ok(BidPreview {
+        commission,
+        total,
+        rejection,
+    })
+}
+
 pub mod query {
-    use cosmwasm_std::{Coin, Deps, StdResult, Uint128};
+    use cosmwasm_std::{Addr, Coin, Deps, Empty, Order, StdResult, Uint128};
+    use cw_storage_plus::{Bound, IndexedMap, Map, PrefixBound};
 
     use crate::{
-        msg::{HighestBidResponse, TotalBidResponse},
-        state::{BIDS, BID_DENOM, BID_OPEN, HIGHEST_BID, HIGHEST_BIDDER},
+        msg::{
+            AddressesResponse, BidderHookResponse, BondResponse, ConfigResponse, DisputeResponse,
+            EpochResponse, EscrowResponse, HighestBidResponse, LeaderboardCursor,
+            LeaderboardResponse, OperatorResponse, OperatorsResponse, RankedBidResponse,
+            SimulateBidResponse, StatsResponse, TotalBidResponse, UnclaimedResponse,
+        },
+        state::{
+            BidIndexes, EscrowStatus, ALLOWLIST, ARBITER, BATCH_EPOCHS, BIDDER_HOOKS, BIDS,
+            BID_DENOM, BID_OPEN, BLOCKLIST, BONDS, BOND_AMOUNT, CLAIM_WINDOW, CLEARED_BIDS,
+            CLOSED_AT, CLOSED_HEIGHT, COMMISSIONS, COMMISSION_PARAMS, DISPUTE, ELIGIBILITY,
+            EPOCH_RESULTS, ESCROW_RELEASE_AT, ESCROW_STATUS, ESCROW_TIMEOUT, FORFEITED, GUARDIAN,
+            HIGHEST_BID, HIGHEST_BIDDER, HOOKS, ITEM, MIN_INCREMENT, OPERATORS, OWNER, PAUSED,
+            PROXY_BIDS, STATS, TIE_BREAK, TOTAL_BIDS, TOTAL_BONDS, TOTAL_COMMISSION, TREASURY,
+        },
     };
 
+    use super::preview_bid;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+
     pub fn highest_bid(deps: Deps) -> StdResult<HighestBidResponse> {
         let bid_open = BID_OPEN.load(deps.storage)?;
         let highest_bid = HIGHEST_BID.load(deps.storage)?;
+        let highest_bidder = HIGHEST_BIDDER.may_load(deps.storage)?;
+
+        highest_bid_response(deps, highest_bid, highest_bidder, !bid_open)
+    }
+
+    pub fn highest_bid_at_height(deps: Deps, height: u64) -> StdResult<HighestBidResponse> {
+        // snapshots return the value from before the given block, the end of `height` is
+        // the start of the next one
+        let at = height.saturating_add(1);
+        let highest_bid = HIGHEST_BID
+            .may_load_at_height(deps.storage, at)?
+            .unwrap_or_default();
+        let highest_bidder = HIGHEST_BIDDER.may_load_at_height(deps.storage, at)?;
+        let bid_closed = CLOSED_HEIGHT
+            .may_load(deps.storage)?
+            .is_some_and(|closed_height| closed_height <= height);
+
+        highest_bid_response(deps, highest_bid, highest_bidder, bid_closed)
+    }
+
+    fn highest_bid_response(
+        deps: Deps,
+        highest_bid: Uint128,
+        highest_bidder: Option<Addr>,
+        bid_closed: bool,
+    ) -> StdResult<HighestBidResponse> {
         let mut resp = HighestBidResponse::default();
 
         if highest_bid > Uint128::new(0) {
@@ -67,13 +332,12 @@ pub mod query {
                 denom: BID_DENOM.load(deps.storage)?,
                 amount: highest_bid,
             });
-            let highest_bidder = HIGHEST_BIDDER.may_load(deps.storage)?;
             if let Some(highest_bidder) = highest_bidder {
                 resp.bidder = Some(highest_bidder.to_string())
             }
         }
 
-        if !bid_open {
+        if bid_closed {
             resp.bid_closed = true;
             resp.winner = resp.bidder.clone();
         }
@@ -84,35 +348,518 @@ pub mod query {
     pub fn total_bid(deps: Deps, addr: String) -> StdResult<TotalBidResponse> {
         let addr = deps.api.addr_validate(&addr)?;
 
-        let mut resp = TotalBidResponse::default();
-        resp.bid_closed = !BID_OPEN.load(deps.storage)?;
+        let mut resp = TotalBidResponse {
+            bid_closed: !BID_OPEN.load(deps.storage)?,
+            ..Default::default()
+        };
 
-        if let Some(amount) = BIDS.may_load(deps.storage, addr)? {
+        if let Some(amount) = BIDS.may_load(deps.storage, addr.clone())? {
             resp.amount = Some(Coin {
                 denom: BID_DENOM.load(deps.storage)?,
                 amount,
             })
         }
 
+        if let Some(amount) = FORFEITED.may_load(deps.storage, addr.clone())? {
+            resp.forfeited = Some(Coin {
+                denom: BID_DENOM.load(deps.storage)?,
+                amount,
+            })
+        }
+
+        if let Some(amount) = PROXY_BIDS.may_load(deps.storage, addr.clone())? {
+            resp.proxy_max = Some(Coin {
+                denom: BID_DENOM.load(deps.storage)?,
+                amount,
+            })
+        }
+
+        if let Some(amount) = COMMISSIONS.may_load(deps.storage, addr)? {
+            resp.commission = Some(Coin {
+                denom: BID_DENOM.load(deps.storage)?,
+                amount,
+            })
+        }
+
         Ok(resp)
     }
+
+    pub fn config(deps: Deps) -> StdResult<ConfigResponse> {
+        let commission_params = COMMISSION_PARAMS.load(deps.storage)?;
+
+        Ok(ConfigResponse {
+            item: ITEM.load(deps.storage)?,
+            bid_denom: BID_DENOM.load(deps.storage)?,
+            owner: OWNER.load(deps.storage)?.into(),
+            commission_minimum_tokens: commission_params.minimum_tokens,
+            commission_part: commission_params.part,
+            commission_tiers: commission_params.tiers,
+            commission_maximum_tokens: commission_params.maximum_tokens,
+            eligibility: ELIGIBILITY.may_load(deps.storage)?.unwrap_or_default(),
+            bond_amount: BOND_AMOUNT.may_load(deps.storage)?,
+            arbiter: ARBITER.may_load(deps.storage)?.map(Addr::into_string),
+            escrow_timeout: ESCROW_TIMEOUT.may_load(deps.storage)?,
+            claim_window: CLAIM_WINDOW.may_load(deps.storage)?,
+            treasury: TREASURY.may_load(deps.storage)?.map(Addr::into_string),
+            min_increment: MIN_INCREMENT.may_load(deps.storage)?,
+            tie_break: TIE_BREAK.may_load(deps.storage)?.unwrap_or_default(),
+            batch_epoch_blocks: BATCH_EPOCHS
+                .may_load(deps.storage)?
+                .map(|batch_epochs| batch_epochs.blocks),
+            guardian: GUARDIAN.may_load(deps.storage)?.map(Addr::into_string),
+            paused: PAUSED.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn stats(deps: Deps) -> StdResult<StatsResponse> {
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+        let coin = |amount| Coin {
+            denom: bid_denom.clone(),
+            amount,
+        };
+
+        // the winning bid stays in the bids total after it is paid out
+        let bid_paid_out = !BID_OPEN.load(deps.storage)?
+            && !matches!(
+                ESCROW_STATUS.may_load(deps.storage)?,
+                Some(EscrowStatus::Pending | EscrowStatus::Disputed)
+            );
+        let mut escrowed = TOTAL_BIDS.may_load(deps.storage)?.unwrap_or_default()
+            + TOTAL_BONDS.may_load(deps.storage)?.unwrap_or_default();
+        if bid_paid_out {
+            escrowed -= HIGHEST_BID.load(deps.storage)?;
+        }
+
+        Ok(StatsResponse {
+            unique_bidders: stats.bidders,
+            bid_count: stats.bids,
+            total_escrowed: coin(escrowed),
+            total_commission: coin(TOTAL_COMMISSION.may_load(deps.storage)?.unwrap_or_default()),
+            total_refunded: coin(stats.refunded),
+            total_paid_to_seller: coin(stats.paid_to_seller),
+            first_bid_height: stats.first_bid_height,
+            last_bid_height: stats.last_bid_height,
+        })
+    }
+
+    pub fn unclaimed(deps: Deps) -> StdResult<UnclaimedResponse> {
+        let claim_until = match (
+            CLOSED_AT.may_load(deps.storage)?,
+            CLAIM_WINDOW.may_load(deps.storage)?,
+        ) {
+            (Some(closed_at), Some(claim_window)) => Some(closed_at.plus_seconds(claim_window)),
+            _ => None,
+        };
+
+        Ok(UnclaimedResponse {
+            amount: Coin {
+                denom: BID_DENOM.load(deps.storage)?,
+                amount: super::unclaimed(deps)?,
+            },
+            claim_until,
+        })
+    }
+
+    pub fn escrow(deps: Deps) -> StdResult<EscrowResponse> {
+        let status = ESCROW_STATUS.may_load(deps.storage)?;
+        if status.is_none() {
+            return Ok(EscrowResponse::default());
+        }
+
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let to_coin = |amount| Coin {
+            denom: bid_denom.clone(),
+            amount,
+        };
+        let dispute = DISPUTE
+            .may_load(deps.storage)?
+            .map(|dispute| DisputeResponse {
+                opened_by: dispute.opened_by.into(),
+                reason: dispute.reason,
+                opened_at: dispute.opened_at,
+                seller_amount: dispute.seller_amount.map(to_coin),
+                buyer_amount: dispute.buyer_amount.map(to_coin),
+            });
+
+        Ok(EscrowResponse {
+            amount: Some(to_coin(HIGHEST_BID.load(deps.storage)?)),
+            status,
+            release_at: ESCROW_RELEASE_AT.may_load(deps.storage)?,
+            dispute,
+        })
+    }
+
+    pub fn bond(deps: Deps, addr: String) -> StdResult<BondResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let to_coin = |amount| Coin {
+            denom: bid_denom.clone(),
+            amount,
+        };
+
+        Ok(BondResponse {
+            required: BOND_AMOUNT.may_load(deps.storage)?.map(to_coin),
+            posted: BONDS.may_load(deps.storage, addr)?.map(to_coin),
+        })
+    }
+
+    fn addresses(
+        deps: Deps,
+        map: Map<Addr, Empty>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let addresses = map
+            .keys(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|addr| addr.map(Addr::into_string))
+            .collect::<StdResult<_>>()?;
+
+        Ok(AddressesResponse { addresses })
+    }
+
+    pub fn allowlist(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        addresses(deps, ALLOWLIST, start_after, limit)
+    }
+
+    pub fn blocklist(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        addresses(deps, BLOCKLIST, start_after, limit)
+    }
+
+    pub fn hooks(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        addresses(deps, HOOKS, start_after, limit)
+    }
+
+    pub fn bidder_hook(deps: Deps, addr: String) -> StdResult<BidderHookResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let hook = BIDDER_HOOKS.may_load(deps.storage, addr)?;
+
+        Ok(BidderHookResponse {
+            hook: hook.map(Addr::into_string),
+        })
+    }
+
+    pub fn operators(
+        deps: Deps,
+        bidder: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorsResponse> {
+        let bidder = deps.api.addr_validate(&bidder)?;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+
+        let operators = OPERATORS
+            .prefix(bidder)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| {
+                let (operator, approval) = item?;
+                Ok(OperatorResponse {
+                    operator: operator.into(),
+                    spend_limit: approval.spend_limit.map(|amount| Coin {
+                        denom: bid_denom.clone(),
+                        amount,
+                    }),
+                    spent: Coin {
+                        denom: bid_denom.clone(),
+                        amount: approval.spent,
+                    },
+                    expires_at: approval.expires_at,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(OperatorsResponse { operators })
+    }
+
+    pub fn leaderboard(
+        deps: Deps,
+        start_after: Option<LeaderboardCursor>,
+        limit: Option<u32>,
+        caller: Option<String>,
+    ) -> StdResult<LeaderboardResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let ranked_bid = |rank, bidder: Addr, amount| RankedBidResponse {
+            rank,
+            bidder: bidder.into(),
+            amount: Coin {
+                denom: bid_denom.clone(),
+                amount,
+            },
+        };
+
+        // batch bids are ranked once their epoch is cleared
+        let ranked_bids = if BATCH_EPOCHS.exists(deps.storage) {
+            &CLEARED_BIDS
+        } else {
+            &BIDS
+        };
+
+        let max = start_after
+            .map(|cursor| -> StdResult<_> {
+                let addr = deps.api.addr_validate(&cursor.bidder)?;
+                Ok(Bound::exclusive((cursor.amount.u128(), addr)))
+            })
+            .transpose()?;
+
+        // bids ahead of the current row, only counted for the first row of the page
+        let mut ahead = 0;
+        let mut bids: Vec<RankedBidResponse> = vec![];
+        for item in ranked_bids
+            .idx
+            .amount
+            .range(deps.storage, None, max, Order::Descending)
+            .take(limit)
+        {
+            let (bidder, amount) = item?;
+            let rank = match bids.last() {
+                Some(prev) if prev.amount.amount == amount => prev.rank,
+                Some(_) => ahead + 1,
+                None => {
+                    let first = Bound::exclusive((amount.u128(), bidder.clone()));
+                    ahead = ranked_bids
+                        .idx
+                        .amount
+                        .keys_raw(deps.storage, Some(first), None, Order::Ascending)
+                        .count() as u32;
+                    bid_rank(deps, ranked_bids, amount)?
+                }
+            };
+            ahead += 1;
+            bids.push(ranked_bid(rank, bidder, amount));
+        }
+
+        let caller = match caller {
+            Some(caller) => {
+                let caller = deps.api.addr_validate(&caller)?;
+                ranked_bids
+                    .may_load(deps.storage, caller.clone())?
+                    .map(|amount| -> StdResult<_> {
+                        Ok(ranked_bid(
+                            bid_rank(deps, ranked_bids, amount)?,
+                            caller,
+                            amount,
+                        ))
+                    })
+                    .transpose()?
+            }
+            None => None,
+        };
+
+        Ok(LeaderboardResponse { bids, caller })
+    }
+
+    pub fn epoch(deps: Deps, epoch: u64) -> StdResult<EpochResponse> {
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let result = EPOCH_RESULTS.load(deps.storage, epoch)?;
+
+        Ok(EpochResponse {
+            epoch,
+            highest_bidder: result.highest_bidder.map(Addr::into_string),
+            highest_bid: (!result.highest_bid.is_zero()).then(|| Coin {
+                denom: bid_denom.clone(),
+                amount: result.highest_bid,
+            }),
+            ranking: result
+                .ranking
+                .into_iter()
+                .zip(1..)
+                .map(|((bidder, amount), rank)| RankedBidResponse {
+                    rank,
+                    bidder: bidder.into(),
+                    amount: Coin {
+                        denom: bid_denom.clone(),
+                        amount,
+                    },
+                })
+                .collect(),
+        })
+    }
+
+    /// One more than the number of `bids` above `amount`.
+    fn bid_rank(
+        deps: Deps,
+        bids: &IndexedMap<Addr, Uint128, BidIndexes>,
+        amount: Uint128,
+    ) -> StdResult<u32> {
+        // raw as `prefix_range` cannot load the bids of a multi index
+        let above = bids
+            .idx
+            .amount
+            .prefix_range_raw(
+                deps.storage,
+                Some(PrefixBound::exclusive(amount.u128())),
+                None,
+                Order::Ascending,
+            )
+            .count();
+        Ok(above as u32 + 1)
+    }
+
+    pub fn simulate_bid(
+        deps: Deps,
+        bidder: String,
+        amount: Uint128,
+    ) -> StdResult<SimulateBidResponse> {
+        let bidder = deps.api.addr_validate(&bidder)?;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let preview = preview_bid(deps, &bidder, amount)?;
+
+        Ok(SimulateBidResponse {
+            commission: Coin {
+                denom: bid_denom.clone(),
+                amount: preview.commission,
+            },
+            total_bid: Coin {
+                denom: bid_denom,
+                amount: preview.total,
+            },
+            accepted: preview.rejection.is_none(),
+            rejection: preview.rejection.map(|err| err.to_string()),
+        })
+    }
 }
 
 pub mod execute {
-    use std::ops::Mul;
-
     use crate::{
         error::ContractError,
-        state::{BIDS, BID_DENOM, BID_OPEN, COMMISSION_PARAMS, HIGHEST_BID, HIGHEST_BIDDER, OWNER},
+        msg::AuctionHookMsg,
+        state::{
+            BidOrder, BidderEligibility, CommissionParams, Dispute, EpochResult, EscrowStatus,
+            OperatorApproval, PauseScope, ALLOWLIST, ARBITER, BATCH_EPOCHS, BIDDER_HOOKS, BIDS,
+            BID_DENOM, BID_OPEN, BID_ORDER, BLOCKLIST, BONDS, BOND_AMOUNT, CLAIM_WINDOW,
+            CLEARED_BIDS, CLOSED_AT, CLOSED_HEIGHT, COMMISSIONS, COMMISSION_PARAMS, DISPUTE,
+            ELIGIBILITY, EPOCH_BIDS, EPOCH_RESULTS, ESCROW_RELEASE_AT, ESCROW_STATUS,
+            ESCROW_TIMEOUT, FORFEITED, GUARDIAN, HIGHEST_BID, HIGHEST_BIDDER, HOOKS, ITEM,
+            MIN_INCREMENT, OPERATORS, OWNER, PAUSED, PROXY_BIDS, STATS, TIE_BREAK, TOTAL_BIDS,
+            TOTAL_BONDS, TOTAL_COMMISSION, TREASURY,
+        },
     };
     use cosmwasm_std::{
-        coins, ensure, BankMsg, Coin, Decimal, DepsMut, Env, MessageInfo, Response, Uint128,
+        coins, ensure, Addr, BankMsg, Coin, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+        Order, Response, StdResult, Storage, Timestamp, Uint128,
     };
+    use cw_storage_plus::PrefixBound;
+
+    use super::{committed_bid, hook_msgs, preview_bid, validate_commission, validate_eligibility};
+
+    const DEFAULT_REFUND_LIMIT: u32 = 10;
+    const MAX_REFUND_LIMIT: u32 = 50;
+
+    /// Loads the approval `bidder` gave `operator`, failing if there is none or it expired.
+    fn ensure_operator(
+        deps: Deps,
+        env: &Env,
+        bidder: &Addr,
+        operator: &Addr,
+    ) -> Result<OperatorApproval, ContractError> {
+        let approval = OPERATORS
+            .may_load(deps.storage, (bidder.clone(), operator.clone()))?
+            .ok_or(ContractError::NotOperator)?;
+        ensure!(
+            !approval.is_expired(env.block.time),
+            ContractError::ApprovalExpired
+        );
+        Ok(approval)
+    }
+
+    fn ensure_owner(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        ensure!(
+            *sender == owner,
+            ContractError::Unauthorized {
+                owner: owner.into()
+            }
+        );
+        Ok(())
+    }
+
+    /// Fails with `Paused` while paused, and the paused `scope` covers the action.
+    fn ensure_not_paused(
+        deps: Deps,
+        scope: impl Fn(&PauseScope) -> bool,
+    ) -> Result<(), ContractError> {
+        let paused = PAUSED.may_load(deps.storage)?;
+        ensure!(!paused.as_ref().is_some_and(scope), ContractError::Paused);
+        Ok(())
+    }
+
+    /// `wasm-auction_refund` event of a bid and bond returned to `bidder`.
+    fn refund_event(
+        env: &Env,
+        bidder: &Addr,
+        beneficiary: &Addr,
+        denom: &str,
+        bid: Uint128,
+        bond: Uint128,
+        was_winner: bool,
+    ) -> Event {
+        Event::new("auction_refund")
+            .add_attribute("auction_id", env.contract.address.as_str())
+            .add_attribute("bidder", bidder.as_str())
+            .add_attribute("beneficiary", beneficiary.as_str())
+            .add_attribute("denom", denom)
+            .add_attribute("bid", bid.to_string())
+            .add_attribute("bond", bond.to_string())
+            .add_attribute("amount", (bid + bond).to_string())
+            .add_attribute("was_winner", was_winner.to_string())
+    }
 
-    pub fn bid(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    /// Places a bid credited to `bidder`, the sender if not given, or with `proxy` a proxy bid:
+    /// the standing bid is raised only as far as needed to lead by `min_increment`, up to the
+    /// committed maximum. Competing proxies are resolved right away, the bidder that loses stands
+    /// at its full maximum so only the highest bidder ever keeps a proxy maximum.
+    pub fn bid(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bidder: Option<String>,
+        proxy: bool,
+    ) -> Result<Response, ContractError> {
         ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+        let epoch = BATCH_EPOCHS
+            .may_load(deps.storage)?
+            .map(|batch_epochs| batch_epochs.epoch(env.block.height));
+        ensure!(!proxy || epoch.is_none(), ContractError::ProxyBidInBatch);
+        let bidder = match bidder {
+            Some(bidder) => deps.api.addr_validate(&bidder)?,
+            None => info.sender.clone(),
+        };
         ensure!(
-            info.sender != OWNER.load(deps.storage)?,
+            bidder != OWNER.load(deps.storage)?,
             ContractError::OwnerCannotBid
         );
 
@@ -123,36 +870,130 @@ pub mod execute {
             ContractError::BidRejectedMissingTokensInDenom { denom: bid_denom }
         );
 
-        let mut amount = bid_funds.unwrap().amount;
-        let commission_params = COMMISSION_PARAMS.load(deps.storage)?;
-        let mut commission_amt = commission_params
-            .part
-            .mul(Decimal::new(amount))
-            .to_uint_ceil();
-
-        if commission_amt < commission_params.minimum_tokens {
-            commission_amt = commission_params.minimum_tokens;
+        let bid_amount = bid_funds.unwrap().amount;
+        let preview = preview_bid(deps.as_ref(), &bidder, bid_amount)?;
+        if let Some(err) = preview.rejection {
+            return Err(err);
         }
-        amount = amount - commission_amt;
 
-        if let Some(prev_total_amount) = BIDS.may_load(deps.storage, info.sender.clone())? {
-            amount += prev_total_amount;
+        // one-off delegated bids need no approval, those of an approved operator are capped by it
+        let operator_key = (bidder.clone(), info.sender.clone());
+        if bidder != info.sender && OPERATORS.has(deps.storage, operator_key.clone()) {
+            let mut approval = ensure_operator(deps.as_ref(), &env, &bidder, &info.sender)?;
+            approval.spent += bid_amount;
+            if let Some(spend_limit) = approval.spend_limit {
+                ensure!(
+                    approval.spent <= spend_limit,
+                    ContractError::SpendLimitExceeded {
+                        remaining: Coin {
+                            denom: bid_denom,
+                            amount: spend_limit - (approval.spent - bid_amount),
+                        }
+                    }
+                );
+            }
+            OPERATORS.save(deps.storage, operator_key, &approval)?;
         }
+        let commission_amt = preview.commission;
+        let max_amount = preview.total;
 
-        let highest_bid_amount = HIGHEST_BID.load(deps.storage)?;
-        ensure!(
-            amount > highest_bid_amount,
-            ContractError::BidRejected {
-                highest_bid: Coin {
-                    amount: highest_bid_amount,
-                    denom: bid_denom
+        let prev_max_amount = committed_bid(deps.as_ref(), &bidder)?;
+        TOTAL_BIDS.update(deps.storage, |total| -> StdResult<_> {
+            Ok(total + max_amount - prev_max_amount)
+        })?;
+
+        let min_increment = MIN_INCREMENT
+            .may_load(deps.storage)?
+            .unwrap_or(Uint128::one());
+        let highest_bid = HIGHEST_BID.load(deps.storage)?;
+        let prev_highest_bidder = HIGHEST_BIDDER.may_load(deps.storage)?;
+
+        let tie_break = TIE_BREAK.may_load(deps.storage)?.unwrap_or_default();
+        let position = (
+            env.block.height,
+            env.transaction.as_ref().map_or(0, |tx| tx.index),
+        );
+        let order = BID_ORDER.update(deps.storage, bidder.clone(), |order| -> StdResult<_> {
+            Ok(BidOrder {
+                first: order.map_or(position, |order| order.first),
+                last: position,
+            })
+        })?;
+
+        // (outbid bidder, new highest bidder, new highest bid)
+        let mut outbid = None;
+        let amount = if let Some(epoch) = epoch {
+            // batch bids compete once their epoch is cleared
+            EPOCH_BIDS.save(deps.storage, (epoch, bidder.clone()), &max_amount)?;
+            max_amount
+        } else {
+            match &prev_highest_bidder {
+                Some(leader) if *leader != bidder => {
+                    let leader_max = PROXY_BIDS
+                        .may_load(deps.storage, leader.clone())?
+                        .unwrap_or(highest_bid);
+                    let takes_tie = max_amount == leader_max
+                        && order.tie_position(&tie_break)
+                            < BID_ORDER
+                                .load(deps.storage, leader.clone())?
+                                .tie_position(&tie_break);
+                    if max_amount > leader_max || takes_tie {
+                        BIDS.save(deps.storage, leader.clone(), &leader_max)?;
+                        PROXY_BIDS.remove(deps.storage, leader.clone());
+
+                        let amount = if proxy {
+                            max_amount.min(leader_max + min_increment)
+                        } else {
+                            max_amount
+                        };
+                        outbid = Some((leader.clone(), bidder.clone(), amount));
+                        amount
+                    } else {
+                        // the leader defends up to its maximum and wins ties by `tie_break`
+                        let leader_amount =
+                            highest_bid.max(leader_max.min(max_amount + min_increment));
+                        BIDS.save(deps.storage, leader.clone(), &leader_amount)?;
+                        if leader_amount == leader_max {
+                            PROXY_BIDS.remove(deps.storage, leader.clone());
+                        }
+                        HIGHEST_BID.save(deps.storage, &leader_amount, env.block.height)?;
+                        outbid = Some((bidder.clone(), leader.clone(), leader_amount));
+                        max_amount
+                    }
                 }
+                Some(_) if proxy => highest_bid,
+                None if proxy => max_amount.min(highest_bid + min_increment),
+                _ => max_amount,
             }
-        );
+        };
+
+        let leads = epoch.is_none()
+            && !matches!(&outbid, Some((outbid_bidder, ..)) if *outbid_bidder == bidder);
+        BIDS.save(deps.storage, bidder.clone(), &amount)?;
+        if proxy && leads && amount < max_amount {
+            PROXY_BIDS.save(deps.storage, bidder.clone(), &max_amount)?;
+        } else {
+            PROXY_BIDS.remove(deps.storage, bidder.clone());
+        }
+        if leads {
+            HIGHEST_BIDDER.save(deps.storage, &bidder, env.block.height)?;
+            HIGHEST_BID.save(deps.storage, &amount, env.block.height)?;
+        }
 
-        HIGHEST_BIDDER.save(deps.storage, &info.sender)?;
-        HIGHEST_BID.save(deps.storage, &amount)?;
-        BIDS.save(deps.storage, info.sender.clone(), &amount)?;
+        let first_bid = !COMMISSIONS.has(deps.storage, bidder.clone());
+        STATS.update(deps.storage, |mut stats| -> StdResult<_> {
+            stats.bids += 1;
+            stats.bidders += u64::from(first_bid);
+            stats.first_bid_height.get_or_insert(env.block.height);
+            stats.last_bid_height = Some(env.block.height);
+            Ok(stats)
+        })?;
+        COMMISSIONS.update(deps.storage, bidder.clone(), |commission| -> StdResult<_> {
+            Ok(commission.unwrap_or_default() + commission_amt)
+        })?;
+        TOTAL_COMMISSION.update(deps.storage, |total| -> StdResult<_> {
+            Ok(total + commission_amt)
+        })?;
 
         let mut resp = Response::new();
         if commission_amt.gt(&Uint128::new(0)) {
@@ -163,16 +1004,50 @@ pub mod execute {
             resp = resp.add_message(commission_msg)
         }
 
+        let mut event = Event::new("auction_bid")
+            .add_attribute("auction_id", env.contract.address.as_str())
+            .add_attribute("bidder", bidder.as_str())
+            .add_attribute("payer", info.sender.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("amount", bid_amount.to_string())
+            .add_attribute("commission", commission_amt.to_string())
+            .add_attribute("total_bid", amount.to_string())
+            .add_attribute("max_bid", max_amount.to_string())
+            .add_attribute("highest_bid", HIGHEST_BID.load(deps.storage)?.to_string());
+        if let Some(highest_bidder) = HIGHEST_BIDDER.may_load(deps.storage)? {
+            event = event.add_attribute("highest_bidder", highest_bidder);
+        }
+        if let Some(epoch) = epoch {
+            event = event.add_attribute("epoch", epoch.to_string());
+        }
+        if let Some(prev_highest_bidder) = prev_highest_bidder {
+            event = event.add_attribute("previous_highest_bidder", prev_highest_bidder);
+        }
+
+        if let Some((outbid_bidder, highest_bidder, highest_bid)) = outbid {
+            let hook_msg = AuctionHookMsg::Outbid {
+                previous: outbid_bidder.to_string(),
+                new: highest_bidder.to_string(),
+                amount: Coin {
+                    denom: bid_denom.clone(),
+                    amount: highest_bid,
+                },
+            };
+            resp = resp.add_submessages(hook_msgs(deps.as_ref(), hook_msg, Some(&outbid_bidder))?);
+        }
+
         resp = resp
-            .add_attribute("action", "bid")
+            .add_attribute("action", if proxy { "proxy_bid" } else { "bid" })
             .add_attribute("sender", info.sender.to_string())
+            .add_attribute("bidder", bidder.as_str())
             .add_attribute("bid_denom", bid_denom)
-            .add_attribute("total_bid_amount", amount.to_string());
+            .add_attribute("total_bid_amount", amount.to_string())
+            .add_event(event);
 
         Ok(resp)
     }
 
-    pub fn close(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    pub fn close(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
         ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
 
         let owner = OWNER.load(deps.storage)?;
@@ -183,23 +1058,96 @@ pub mod execute {
             }
         );
 
+        ensure_not_paused(deps.as_ref(), |scope| scope.close)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "close")
+            .add_attribute("sender", info.sender.as_str());
+        close_bidding(deps, env, resp)
+    }
+
+    /// Closes bidding for the owner or chain governance, the highest bid is escrowed or paid to
+    /// the owner.
+    fn close_bidding(
+        mut deps: DepsMut,
+        env: Env,
+        resp: Response,
+    ) -> Result<Response, ContractError> {
+        // pending batch bids, of the current epoch as well, are cleared before the winner is taken
+        let cleared = clear_epochs(deps.branch(), &env, u64::MAX)?;
+
         BID_OPEN.save(deps.storage, &false)?;
+        CLOSED_AT.save(deps.storage, &env.block.time)?;
+        CLOSED_HEIGHT.save(deps.storage, &env.block.height)?;
 
+        let owner = OWNER.load(deps.storage)?;
         let bid_denom = BID_DENOM.load(deps.storage)?;
-        let mut resp = Response::new()
-            .add_attribute("action", "close")
-            .add_attribute("sender", info.sender.as_str())
+        let mut resp = resp
+            .add_events(cleared.events)
+            .add_submessages(cleared.messages)
             .add_attribute("bid_denom", &bid_denom);
 
         let closing_bid = HIGHEST_BID.load(deps.storage)?;
+        let escrowed = ARBITER.exists(deps.storage) && !closing_bid.is_zero();
+        let mut event = Event::new("auction_closed")
+            .add_attribute("auction_id", env.contract.address.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("closing_bid", closing_bid.to_string())
+            .add_attribute("escrowed", escrowed.to_string());
         if closing_bid.u128() == 0 {
-            return Ok(resp);
+            let hook_msg = AuctionHookMsg::Closed {
+                winner: None,
+                amount: None,
+            };
+            return Ok(resp.add_event(event).add_submessages(hook_msgs(
+                deps.as_ref(),
+                hook_msg,
+                None,
+            )?));
         }
 
+        let winner = HIGHEST_BIDDER.load(deps.storage)?;
+        let hook_msg = AuctionHookMsg::Closed {
+            winner: Some(winner.to_string()),
+            amount: Some(Coin {
+                denom: bid_denom.clone(),
+                amount: closing_bid,
+            }),
+        };
+        event = event.add_attribute("winner", winner.as_str());
         resp = resp
             .add_attribute("closing_bid", closing_bid.to_string())
-            .add_attribute("winner", HIGHEST_BIDDER.load(deps.storage)?);
+            .add_attribute("winner", winner.as_str())
+            .add_event(event)
+            .add_submessages(hook_msgs(deps.as_ref(), hook_msg, Some(&winner))?);
+
+        // the unused part of a proxy maximum goes back to the winner
+        if let Some(max_amount) = PROXY_BIDS.may_load(deps.storage, winner.clone())? {
+            let proxy_refund = max_amount - closing_bid;
+            PROXY_BIDS.remove(deps.storage, winner.clone());
+            TOTAL_BIDS.update(deps.storage, |total| -> StdResult<_> {
+                Ok(total - proxy_refund)
+            })?;
+            record_payout(deps.storage, proxy_refund, Uint128::zero())?;
+            resp = resp.add_attribute("proxy_refund", proxy_refund.to_string());
+            if !proxy_refund.is_zero() {
+                resp = resp.add_message(BankMsg::Send {
+                    to_address: winner.to_string(),
+                    amount: coins(proxy_refund.u128(), &bid_denom),
+                });
+            }
+        }
+
+        if escrowed {
+            ESCROW_STATUS.save(deps.storage, &EscrowStatus::Pending)?;
+            if let Some(escrow_timeout) = ESCROW_TIMEOUT.may_load(deps.storage)? {
+                let release_at = env.block.time.plus_seconds(escrow_timeout);
+                ESCROW_RELEASE_AT.save(deps.storage, &release_at)?;
+            }
+            return Ok(resp.add_attribute("escrowed", "true"));
+        }
 
+        record_payout(deps.storage, Uint128::zero(), closing_bid)?;
         let bank_msg = BankMsg::Send {
             to_address: owner.to_string(),
             amount: coins(closing_bid.u128(), bid_denom),
@@ -209,34 +1157,279 @@ pub mod execute {
         Ok(resp)
     }
 
-    pub fn retract(
+    /// Governance close, the same as the owner closing.
+    pub fn force_close(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+
+        let resp = Response::new().add_attribute("action", "force_close");
+        close_bidding(deps, env, resp)
+    }
+
+    /// Governance cancel: bidding is closed without a winner and up to `limit` bidders and
+    /// bond holders are refunded, the rest can be refunded with `refund_batch`.
+    pub fn force_cancel(
         deps: DepsMut,
-        info: MessageInfo,
-        receiver: Option<String>,
+        env: Env,
+        limit: Option<u32>,
     ) -> Result<Response, ContractError> {
-        ensure!(!BID_OPEN.load(deps.storage)?, ContractError::BidOpen);
-
-        let mut resp = Response::new()
-            .add_attribute("action", "retract")
-            .add_attribute("sender", info.sender.as_str());
-        let mut beneficiary = info.sender;
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
 
-        let amount = BIDS.may_load(deps.storage, beneficiary.clone())?;
-        if amount.is_none() {
-            return Ok(resp);
+        BID_OPEN.save(deps.storage, &false)?;
+        CLOSED_AT.save(deps.storage, &env.block.time)?;
+        CLOSED_HEIGHT.save(deps.storage, &env.block.height)?;
+        HIGHEST_BIDDER.remove(deps.storage, env.block.height)?;
+        HIGHEST_BID.save(deps.storage, &Uint128::zero(), env.block.height)?;
+        // batch bids left pending are refunded like any other bid
+        let pending: Vec<_> = EPOCH_BIDS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for key in pending {
+            EPOCH_BIDS.remove(deps.storage, key);
         }
 
-        let amount = amount.unwrap();
-        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let hook_msg = AuctionHookMsg::Closed {
+            winner: None,
+            amount: None,
+        };
+        let resp = Response::new()
+            .add_attribute("action", "force_cancel")
+            .add_event(
+                Event::new("auction_cancelled")
+                    .add_attribute("auction_id", env.contract.address.as_str()),
+            )
+            .add_submessages(hook_msgs(deps.as_ref(), hook_msg, None)?);
+        refund_unclaimed(deps, &env, limit, resp)
+    }
 
-        if let Some(receiver) = receiver {
-            beneficiary = deps.api.addr_validate(&receiver)?;
+    /// Governance transfer of the auction to a new owner.
+    pub fn change_owner(deps: DepsMut, owner: String) -> Result<Response, ContractError> {
+        let owner = deps.api.addr_validate(&owner)?;
+        if BID_OPEN.load(deps.storage)? {
+            let is_bidder = BIDS.has(deps.storage, owner.clone())
+                || BONDS.has(deps.storage, owner.clone())
+                || HIGHEST_BIDDER.may_load(deps.storage)?.as_ref() == Some(&owner);
+            ensure!(!is_bidder, ContractError::OwnerCannotBid);
+        }
+        OWNER.save(deps.storage, &owner)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "change_owner")
+            .add_attribute("owner", owner.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn crank(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+        let batch_epochs = BATCH_EPOCHS
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NotBatchAuction)?;
+
+        let epoch = batch_epochs.epoch(env.block.height);
+        let resp = clear_epochs(deps, &env, epoch)?
+            .add_attribute("action", "crank")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("epoch", epoch.to_string());
+
+        Ok(resp)
+    }
+
+    /// Clears the batch epochs before `until` in order. The best bid of an epoch by amount and
+    /// `tie_break` takes the lead if it beats the highest bid so far, bids retracted since do
+    /// not compete. The highest bid after the epoch and its ranked bids are stored.
+    fn clear_epochs(deps: DepsMut, env: &Env, until: u64) -> Result<Response, ContractError> {
+        let pending = EPOCH_BIDS
+            .prefix_range(
+                deps.storage,
+                None,
+                Some(PrefixBound::exclusive(until)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut epochs: Vec<(u64, Vec<(Addr, Uint128)>)> = vec![];
+        for ((epoch, bidder), amount) in pending {
+            EPOCH_BIDS.remove(deps.storage, (epoch, bidder.clone()));
+            match epochs.last_mut() {
+                Some((last, bids)) if *last == epoch => bids.push((bidder, amount)),
+                _ => epochs.push((epoch, vec![(bidder, amount)])),
+            }
+        }
+
+        let tie_break = TIE_BREAK.may_load(deps.storage)?.unwrap_or_default();
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let mut resp = Response::new();
+        for (epoch, bids) in epochs {
+            let mut ranking = vec![];
+            for (bidder, amount) in bids {
+                let standing = BIDS.may_load(deps.storage, bidder.clone())?;
+                if standing.is_some_and(|standing| standing >= amount) {
+                    CLEARED_BIDS.save(deps.storage, bidder.clone(), &amount)?;
+                    let position = BID_ORDER
+                        .load(deps.storage, bidder.clone())?
+                        .tie_position(&tie_break);
+                    ranking.push((bidder, amount, position));
+                }
+            }
+            ranking.sort_by(|(_, amount, position), (_, other_amount, other_position)| {
+                other_amount
+                    .cmp(amount)
+                    .then_with(|| position.cmp(other_position))
+            });
+
+            let highest_bid = HIGHEST_BID.load(deps.storage)?;
+            let leader = HIGHEST_BIDDER.may_load(deps.storage)?;
+            if let Some((bidder, amount, position)) = ranking.first() {
+                let leads = match &leader {
+                    Some(leader) if leader != bidder && *amount == highest_bid => {
+                        *position
+                            < BID_ORDER
+                                .load(deps.storage, leader.clone())?
+                                .tie_position(&tie_break)
+                    }
+                    _ => *amount > highest_bid,
+                };
+                if leads {
+                    HIGHEST_BIDDER.save(deps.storage, bidder, env.block.height)?;
+                    HIGHEST_BID.save(deps.storage, amount, env.block.height)?;
+                    if let Some(leader) = leader.filter(|leader| leader != bidder) {
+                        let hook_msg = AuctionHookMsg::Outbid {
+                            previous: leader.to_string(),
+                            new: bidder.to_string(),
+                            amount: Coin {
+                                denom: bid_denom.clone(),
+                                amount: *amount,
+                            },
+                        };
+                        resp = resp.add_submessages(hook_msgs(
+                            deps.as_ref(),
+                            hook_msg,
+                            Some(&leader),
+                        )?);
+                    }
+                }
+            }
+
+            let result = EpochResult {
+                highest_bidder: HIGHEST_BIDDER.may_load(deps.storage)?,
+                highest_bid: HIGHEST_BID.load(deps.storage)?,
+                ranking: ranking
+                    .into_iter()
+                    .map(|(bidder, amount, _)| (bidder, amount))
+                    .collect(),
+            };
+            EPOCH_RESULTS.save(deps.storage, epoch, &result)?;
+
+            let mut event = Event::new("auction_epoch_cleared")
+                .add_attribute("auction_id", env.contract.address.as_str())
+                .add_attribute("epoch", epoch.to_string())
+                .add_attribute("denom", &bid_denom)
+                .add_attribute("bids", result.ranking.len().to_string())
+                .add_attribute("highest_bid", result.highest_bid.to_string());
+            if let Some(highest_bidder) = result.highest_bidder {
+                event = event.add_attribute("highest_bidder", highest_bidder);
+            }
+            resp = resp.add_event(event);
+        }
+
+        Ok(resp)
+    }
+
+    /// Adds tokens returned to bidders and paid to the owner to the auction stats.
+    fn record_payout(
+        storage: &mut dyn Storage,
+        refunded: Uint128,
+        paid_to_seller: Uint128,
+    ) -> StdResult<()> {
+        STATS.update(storage, |mut stats| -> StdResult<_> {
+            stats.refunded += refunded;
+            stats.paid_to_seller += paid_to_seller;
+            Ok(stats)
+        })?;
+        Ok(())
+    }
+
+    /// Removes the total bid of `bidder`, including an unused proxy maximum, and the bond if
+    /// `with_bond` is set, returning both amounts to refund.
+    fn take_refund(
+        storage: &mut dyn Storage,
+        bidder: &Addr,
+        with_bond: bool,
+    ) -> StdResult<(Uint128, Uint128)> {
+        let bid = match PROXY_BIDS.may_load(storage, bidder.clone())? {
+            Some(max_amount) => max_amount,
+            None => BIDS.may_load(storage, bidder.clone())?.unwrap_or_default(),
+        };
+        BIDS.remove(storage, bidder.clone())?;
+        PROXY_BIDS.remove(storage, bidder.clone());
+        BID_ORDER.remove(storage, bidder.clone());
+        CLEARED_BIDS.remove(storage, bidder.clone())?;
+        TOTAL_BIDS.update(storage, |total| -> StdResult<_> { Ok(total - bid) })?;
+
+        let mut bond = Uint128::zero();
+        if with_bond {
+            bond = BONDS.may_load(storage, bidder.clone())?.unwrap_or_default();
+            BONDS.remove(storage, bidder.clone());
+            TOTAL_BONDS.update(storage, |total| -> StdResult<_> { Ok(total - bond) })?;
+        }
+
+        Ok((bid, bond))
+    }
+
+    pub fn retract(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receiver: Option<String>,
+        bidder: Option<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.as_ref(), |scope| scope.retract)?;
+        let bidder = match bidder {
+            Some(bidder) => deps.api.addr_validate(&bidder)?,
+            None => info.sender.clone(),
+        };
+        if bidder != info.sender {
+            ensure_operator(deps.as_ref(), &env, &bidder, &info.sender)?;
+        }
+
+        // while open only outbid bidders can withdraw, and their bond stays posted so they
+        // can re-enter from zero
+        let bid_open = BID_OPEN.load(deps.storage)?;
+        let is_highest_bidder = HIGHEST_BIDDER.may_load(deps.storage)?.as_ref() == Some(&bidder);
+        ensure!(!(bid_open && is_highest_bidder), ContractError::BidOpen);
+        ensure!(!is_highest_bidder, ContractError::WinnerCannotRetract);
+
+        let mut resp = Response::new()
+            .add_attribute("action", "retract")
+            .add_attribute("sender", info.sender.as_str());
+        let mut beneficiary = bidder.clone();
+
+        let (bid, bond) = take_refund(deps.storage, &bidder, !bid_open)?;
+        if bid.is_zero() && bond.is_zero() {
+            return Ok(resp);
+        }
+
+        let amount = bid + bond;
+        record_payout(deps.storage, amount, Uint128::zero())?;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+
+        if let Some(receiver) = receiver {
+            beneficiary = deps.api.addr_validate(&receiver)?;
         }
 
         resp = resp
             .add_attribute("denom", &bid_denom)
             .add_attribute("amount", amount.to_string())
-            .add_attribute("beneficiary", beneficiary.as_str());
+            .add_attribute("bond", bond.to_string())
+            .add_attribute("beneficiary", beneficiary.as_str())
+            .add_event(refund_event(
+                &env,
+                &bidder,
+                &beneficiary,
+                &bid_denom,
+                bid,
+                bond,
+                is_highest_bidder,
+            ));
 
         let msg = BankMsg::Send {
             to_address: beneficiary.to_string(),
@@ -245,4 +1438,774 @@ pub mod execute {
 
         Ok(resp.add_message(msg))
     }
+
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        item: Option<String>,
+        bid_denom: Option<String>,
+        commission: Option<CommissionParams>,
+    ) -> Result<Response, ContractError> {
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let has_bids = !BIDS.is_empty(deps.storage) || !BONDS.is_empty(deps.storage);
+        let mut resp = Response::new()
+            .add_attribute("action", "update_config")
+            .add_attribute("sender", info.sender.as_str());
+
+        if let Some(item) = item {
+            ensure!(
+                !has_bids || item == ITEM.load(deps.storage)?,
+                ContractError::ConfigLocked {
+                    field: "item".to_owned()
+                }
+            );
+            ITEM.save(deps.storage, &item)?;
+            resp = resp.add_attribute("item", item);
+        }
+
+        if let Some(bid_denom) = bid_denom {
+            ensure!(
+                !has_bids || bid_denom == BID_DENOM.load(deps.storage)?,
+                ContractError::ConfigLocked {
+                    field: "bid_denom".to_owned()
+                }
+            );
+            BID_DENOM.save(deps.storage, &bid_denom)?;
+            resp = resp.add_attribute("bid_denom", bid_denom);
+        }
+
+        if let Some(commission) = commission {
+            validate_commission(&commission)?;
+            ensure!(
+                !has_bids || commission.never_exceeds(&COMMISSION_PARAMS.load(deps.storage)?),
+                ContractError::ConfigLocked {
+                    field: "commission".to_owned()
+                }
+            );
+            COMMISSION_PARAMS.save(deps.storage, &commission)?;
+            resp = resp.add_attribute("commission_updated", "true");
+        }
+
+        Ok(resp)
+    }
+
+    pub fn set_eligibility(
+        deps: DepsMut,
+        info: MessageInfo,
+        eligibility: BidderEligibility,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let eligibility = validate_eligibility(deps.as_ref(), eligibility)?;
+        ELIGIBILITY.save(deps.storage, &eligibility)?;
+
+        let mode = match eligibility {
+            BidderEligibility::Anyone => "anyone".to_owned(),
+            BidderEligibility::Allowlist => "allowlist".to_owned(),
+            BidderEligibility::Registry { contract } => format!("registry:{contract}"),
+        };
+        let resp = Response::new()
+            .add_attribute("action", "set_eligibility")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("eligibility", mode);
+
+        Ok(resp)
+    }
+
+    pub fn allow_bidders(
+        deps: DepsMut,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        for addr in &addresses {
+            let addr = deps.api.addr_validate(addr)?;
+            ALLOWLIST.save(deps.storage, addr, &Empty {})?;
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "allow_bidders")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("addresses", addresses.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn disallow_bidders(
+        deps: DepsMut,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        for addr in &addresses {
+            let addr = deps.api.addr_validate(addr)?;
+            ALLOWLIST.remove(deps.storage, addr);
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "disallow_bidders")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("addresses", addresses.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn block_bidders(
+        deps: DepsMut,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        for addr in &addresses {
+            let addr = deps.api.addr_validate(addr)?;
+            BLOCKLIST.save(deps.storage, addr, &Empty {})?;
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "block_bidders")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("addresses", addresses.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn unblock_bidders(
+        deps: DepsMut,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        for addr in &addresses {
+            let addr = deps.api.addr_validate(addr)?;
+            BLOCKLIST.remove(deps.storage, addr);
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "unblock_bidders")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("addresses", addresses.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn add_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.save(deps.storage, hook, &Empty {})?;
+
+        let resp = Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("hook", addr);
+
+        Ok(resp)
+    }
+
+    pub fn pause(
+        deps: DepsMut,
+        info: MessageInfo,
+        mut scope: PauseScope,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        let guardian = GUARDIAN.may_load(deps.storage)?;
+        ensure!(
+            info.sender == owner || Some(&info.sender) == guardian.as_ref(),
+            ContractError::NotPauser
+        );
+
+        // only the owner unpauses, so the guardian can widen the scope but not narrow it
+        if info.sender != owner {
+            if let Some(paused) = PAUSED.may_load(deps.storage)? {
+                scope.close |= paused.close;
+                scope.retract |= paused.retract;
+            }
+        }
+        PAUSED.save(deps.storage, &scope)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("close", scope.close.to_string())
+            .add_attribute("retract", scope.retract.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        PAUSED.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "unpause")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn remove_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.remove(deps.storage, hook);
+
+        let resp = Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("hook", addr);
+
+        Ok(resp)
+    }
+
+    pub fn approve_operator(
+        deps: DepsMut,
+        info: MessageInfo,
+        operator: String,
+        spend_limit: Option<Uint128>,
+        expires_at: Option<Timestamp>,
+    ) -> Result<Response, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        let approval = OperatorApproval {
+            spend_limit,
+            spent: Uint128::zero(),
+            expires_at,
+        };
+        OPERATORS.save(
+            deps.storage,
+            (info.sender.clone(), operator_addr),
+            &approval,
+        )?;
+
+        let resp = Response::new()
+            .add_attribute("action", "approve_operator")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("operator", operator);
+
+        Ok(resp)
+    }
+
+    pub fn revoke_operator(
+        deps: DepsMut,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        OPERATORS.remove(deps.storage, (info.sender.clone(), operator_addr));
+
+        let resp = Response::new()
+            .add_attribute("action", "revoke_operator")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("operator", operator);
+
+        Ok(resp)
+    }
+
+    pub fn set_bidder_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let mut resp = Response::new()
+            .add_attribute("action", "set_bidder_hook")
+            .add_attribute("sender", info.sender.as_str());
+
+        match addr {
+            Some(addr) => {
+                let hook = deps.api.addr_validate(&addr)?;
+                BIDDER_HOOKS.save(deps.storage, info.sender, &hook)?;
+                resp = resp.add_attribute("hook", addr);
+            }
+            None => BIDDER_HOOKS.remove(deps.storage, info.sender),
+        }
+
+        Ok(resp)
+    }
+
+    pub fn post_bond(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+        ensure!(
+            info.sender != OWNER.load(deps.storage)?,
+            ContractError::OwnerCannotBid
+        );
+
+        let bond_amount = BOND_AMOUNT
+            .may_load(deps.storage)?
+            .ok_or(ContractError::BondNotRequired)?;
+        ensure!(
+            !BONDS.has(deps.storage, info.sender.clone()),
+            ContractError::BondAlreadyPosted
+        );
+
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let bond = Coin {
+            denom: bid_denom.clone(),
+            amount: bond_amount,
+        };
+        ensure!(
+            info.funds == [bond.clone()],
+            ContractError::BondRejected { bond }
+        );
+
+        BONDS.save(deps.storage, info.sender.clone(), &bond_amount)?;
+        TOTAL_BONDS.update(deps.storage, |total| -> StdResult<_> {
+            Ok(total + bond_amount)
+        })?;
+
+        let resp = Response::new()
+            .add_attribute("action", "post_bond")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("denom", bid_denom)
+            .add_attribute("bond", bond_amount.to_string());
+
+        Ok(resp)
+    }
+
+    /// Returns the winner's bond to the winner, or to the owner if `slash` is set.
+    pub fn settle_bond(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        slash: bool,
+    ) -> Result<Response, ContractError> {
+        ensure!(!BID_OPEN.load(deps.storage)?, ContractError::BidOpen);
+        let escrow_released = ESCROW_STATUS.may_load(deps.storage)? == Some(EscrowStatus::Released);
+        let winner = HIGHEST_BIDDER.may_load(deps.storage)?;
+        if slash || winner.as_ref() != Some(&info.sender) {
+            ensure_owner(deps.as_ref(), &info.sender)?;
+            ensure!(!slash || !escrow_released, ContractError::BondReleased);
+        } else {
+            // the winner reclaims it once the escrow is released or the claim window is over
+            let claim_over = match CLAIM_WINDOW.may_load(deps.storage)? {
+                Some(claim_window) => {
+                    env.block.time >= CLOSED_AT.load(deps.storage)?.plus_seconds(claim_window)
+                }
+                None => false,
+            };
+            ensure!(escrow_released || claim_over, ContractError::BondLocked);
+        }
+
+        let winner = winner.ok_or(ContractError::NoWinnerBond)?;
+        let bond = BONDS
+            .may_load(deps.storage, winner.clone())?
+            .ok_or(ContractError::NoWinnerBond)?;
+        BONDS.remove(deps.storage, winner.clone());
+        TOTAL_BONDS.update(deps.storage, |total| -> StdResult<_> { Ok(total - bond) })?;
+
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let beneficiary = if slash {
+            info.sender.clone()
+        } else {
+            winner.clone()
+        };
+        let mut resp = Response::new()
+            .add_attribute("action", if slash { "slash_bond" } else { "release_bond" })
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("bond", bond.to_string())
+            .add_attribute("beneficiary", beneficiary.as_str())
+            .add_message(BankMsg::Send {
+                to_address: beneficiary.to_string(),
+                amount: coins(bond.u128(), &bid_denom),
+            });
+        if !slash {
+            record_payout(deps.storage, bond, Uint128::zero())?;
+            resp = resp.add_event(refund_event(
+                &env,
+                &winner,
+                &beneficiary,
+                &bid_denom,
+                Uint128::zero(),
+                bond,
+                true,
+            ));
+        }
+
+        Ok(resp)
+    }
+
+    /// Loads the escrow that is still waiting to be settled.
+    fn unsettled_escrow(deps: Deps) -> Result<EscrowStatus, ContractError> {
+        let status = ESCROW_STATUS
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoEscrow)?;
+        ensure!(
+            matches!(status, EscrowStatus::Pending | EscrowStatus::Disputed),
+            ContractError::EscrowSettled
+        );
+        Ok(status)
+    }
+
+    /// Sends `seller_amount` of the escrowed winning bid to the owner and the rest back to the
+    /// winner.
+    fn settle_escrow(
+        deps: DepsMut,
+        action: &str,
+        sender: &Addr,
+        status: EscrowStatus,
+        seller_amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        ESCROW_STATUS.save(deps.storage, &status)?;
+
+        let amount = HIGHEST_BID.load(deps.storage)?;
+        let buyer_amount = amount - seller_amount;
+        record_payout(deps.storage, buyer_amount, seller_amount)?;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let mut resp = Response::new()
+            .add_attribute("action", action)
+            .add_attribute("sender", sender.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("seller_amount", seller_amount.to_string())
+            .add_attribute("buyer_amount", buyer_amount.to_string());
+
+        for (beneficiary, amount) in [
+            (OWNER.load(deps.storage)?, seller_amount),
+            (HIGHEST_BIDDER.load(deps.storage)?, buyer_amount),
+        ] {
+            if !amount.is_zero() {
+                resp = resp.add_message(BankMsg::Send {
+                    to_address: beneficiary.into(),
+                    amount: coins(amount.u128(), &bid_denom),
+                });
+            }
+        }
+
+        Ok(resp)
+    }
+
+    pub fn confirm_delivery(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        unsettled_escrow(deps.as_ref())?;
+        ensure!(
+            info.sender == HIGHEST_BIDDER.load(deps.storage)?,
+            ContractError::NotWinner
+        );
+
+        let amount = HIGHEST_BID.load(deps.storage)?;
+        settle_escrow(
+            deps,
+            "confirm_delivery",
+            &info.sender,
+            EscrowStatus::Released,
+            amount,
+        )
+    }
+
+    pub fn open_dispute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        reason: String,
+    ) -> Result<Response, ContractError> {
+        let status = unsettled_escrow(deps.as_ref())?;
+        ensure!(
+            info.sender == HIGHEST_BIDDER.load(deps.storage)?
+                || info.sender == OWNER.load(deps.storage)?,
+            ContractError::NotEscrowParty
+        );
+        ensure!(
+            status == EscrowStatus::Pending,
+            ContractError::EscrowDisputed
+        );
+        if let Some(release_at) = ESCROW_RELEASE_AT.may_load(deps.storage)? {
+            ensure!(
+                env.block.time < release_at,
+                ContractError::EscrowExpired { release_at }
+            );
+        }
+
+        ESCROW_STATUS.save(deps.storage, &EscrowStatus::Disputed)?;
+        DISPUTE.save(
+            deps.storage,
+            &Dispute {
+                opened_by: info.sender.clone(),
+                reason: reason.clone(),
+                opened_at: env.block.time,
+                seller_amount: None,
+                buyer_amount: None,
+            },
+        )?;
+
+        let resp = Response::new()
+            .add_attribute("action", "open_dispute")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("reason", reason);
+
+        Ok(resp)
+    }
+
+    pub fn resolve(
+        deps: DepsMut,
+        info: MessageInfo,
+        seller_share: Decimal,
+    ) -> Result<Response, ContractError> {
+        let status = unsettled_escrow(deps.as_ref())?;
+        ensure!(
+            info.sender == ARBITER.load(deps.storage)?,
+            ContractError::NotArbiter
+        );
+        ensure!(
+            status == EscrowStatus::Disputed,
+            ContractError::EscrowNotDisputed
+        );
+        ensure!(
+            seller_share <= Decimal::one(),
+            ContractError::InvalidSellerShare
+        );
+
+        let amount = HIGHEST_BID.load(deps.storage)?;
+        let seller_amount = amount * seller_share;
+        DISPUTE.update(deps.storage, |mut dispute| -> StdResult<_> {
+            dispute.seller_amount = Some(seller_amount);
+            dispute.buyer_amount = Some(amount - seller_amount);
+            Ok(dispute)
+        })?;
+
+        settle_escrow(
+            deps,
+            "resolve",
+            &info.sender,
+            EscrowStatus::Resolved,
+            seller_amount,
+        )
+    }
+
+    pub fn release_escrow(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let status = unsettled_escrow(deps.as_ref())?;
+        ensure!(
+            status == EscrowStatus::Pending,
+            ContractError::EscrowDisputed
+        );
+
+        let release_at = ESCROW_RELEASE_AT
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoEscrowTimeout)?;
+        ensure!(
+            env.block.time >= release_at,
+            ContractError::EscrowLocked { release_at }
+        );
+
+        let amount = HIGHEST_BID.load(deps.storage)?;
+        settle_escrow(
+            deps,
+            "release_escrow",
+            &info.sender,
+            EscrowStatus::Released,
+            amount,
+        )
+    }
+
+    /// Up to `limit` bidders and bond holders, except the winner, with funds left in the contract.
+    fn unclaimed_bidders(deps: Deps, limit: Option<u32>) -> StdResult<Vec<Addr>> {
+        let limit = limit.unwrap_or(DEFAULT_REFUND_LIMIT).min(MAX_REFUND_LIMIT) as usize;
+        let winner = HIGHEST_BIDDER.may_load(deps.storage)?;
+        let not_winner = |addr: &StdResult<Addr>| addr.as_ref().ok() != winner.as_ref();
+
+        let mut bidders: Vec<Addr> = BIDS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .filter(not_winner)
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+        if bidders.len() < limit {
+            // bidders with bids left are already in, only bond holders that never bid remain
+            let bond_holders: Vec<Addr> = BONDS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .filter(not_winner)
+                .filter(|addr| !matches!(addr, Ok(addr) if bidders.contains(addr)))
+                .take(limit - bidders.len())
+                .collect::<StdResult<_>>()?;
+            bidders.extend(bond_holders);
+        }
+
+        Ok(bidders)
+    }
+
+    /// Refunds up to `limit` losing bidders and bond holders after close. Refunded entries are
+    /// removed, exactly as on retract, so every batch resumes where the previous one stopped
+    /// and bidders that already retracted are skipped.
+    pub fn refund_batch(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        ensure!(!BID_OPEN.load(deps.storage)?, ContractError::BidOpen);
+        ensure_not_paused(deps.as_ref(), |scope| scope.retract)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "refund_batch")
+            .add_attribute("sender", info.sender.as_str());
+        refund_unclaimed(deps, &env, limit, resp)
+    }
+
+    fn refund_unclaimed(
+        deps: DepsMut,
+        env: &Env,
+        limit: Option<u32>,
+        resp: Response,
+    ) -> Result<Response, ContractError> {
+        let bidders = unclaimed_bidders(deps.as_ref(), limit)?;
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let mut resp = resp
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("refunded", bidders.len().to_string());
+
+        for bidder in bidders {
+            let (bid, bond) = take_refund(deps.storage, &bidder, true)?;
+            record_payout(deps.storage, bid + bond, Uint128::zero())?;
+            resp = resp
+                .add_event(refund_event(
+                    env, &bidder, &bidder, &bid_denom, bid, bond, false,
+                ))
+                .add_message(BankMsg::Send {
+                    to_address: bidder.into(),
+                    amount: coins((bid + bond).u128(), &bid_denom),
+                });
+        }
+
+        Ok(resp)
+    }
+
+    /// Forfeits the funds of up to `limit` bidders that did not claim them within the claim
+    /// window after close and sends them to the treasury. Can be repeated like `refund_batch`.
+    pub fn sweep(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        ensure!(!BID_OPEN.load(deps.storage)?, ContractError::BidOpen);
+        ensure_not_paused(deps.as_ref(), |scope| scope.retract)?;
+
+        let owner = OWNER.load(deps.storage)?;
+        let treasury = TREASURY.may_load(deps.storage)?.unwrap_or(owner.clone());
+        ensure!(
+            info.sender == owner || info.sender == treasury,
+            ContractError::NotSweeper
+        );
+
+        let claim_window = CLAIM_WINDOW
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoClaimWindow)?;
+        let claim_until = CLOSED_AT.load(deps.storage)?.plus_seconds(claim_window);
+        ensure!(
+            env.block.time >= claim_until,
+            ContractError::ClaimWindowOpen { claim_until }
+        );
+
+        let bidders = unclaimed_bidders(deps.as_ref(), limit)?;
+        let mut swept = Uint128::zero();
+        for bidder in &bidders {
+            let (bid, bond) = take_refund(deps.storage, bidder, true)?;
+            FORFEITED.save(deps.storage, bidder.clone(), &(bid + bond))?;
+            swept += bid + bond;
+        }
+
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let mut resp = Response::new()
+            .add_attribute("action", "sweep")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("forfeited", bidders.len().to_string())
+            .add_attribute("amount", swept.to_string())
+            .add_attribute("treasury", treasury.as_str());
+
+        if !swept.is_zero() {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: treasury.into(),
+                amount: coins(swept.u128(), bid_denom),
+            });
+        }
+
+        Ok(resp)
+    }
+
+    pub fn reject_bid(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bidder: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(BID_OPEN.load(deps.storage)?, ContractError::BidClosed);
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let bidder = deps.api.addr_validate(&bidder)?;
+        ensure!(BIDS.has(deps.storage, bidder.clone()), ContractError::NoBid);
+        // the next highest bid may still be waiting for its epoch to be cleared
+        let leads = HIGHEST_BIDDER.may_load(deps.storage)?.as_ref() == Some(&bidder);
+        ensure!(
+            !leads || EPOCH_BIDS.is_empty(deps.storage),
+            ContractError::EpochPending
+        );
+        let (bid, bond) = take_refund(deps.storage, &bidder, false)?;
+        record_payout(deps.storage, bid, Uint128::zero())?;
+
+        let bid_denom = BID_DENOM.load(deps.storage)?;
+        let mut resp = Response::new()
+            .add_attribute("action", "reject_bid")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("bidder", bidder.as_str())
+            .add_attribute("denom", &bid_denom)
+            .add_attribute("amount", bid.to_string())
+            .add_event(refund_event(
+                &env, &bidder, &bidder, &bid_denom, bid, bond, false,
+            ))
+            .add_message(BankMsg::Send {
+                to_address: bidder.to_string(),
+                amount: coins(bid.u128(), &bid_denom),
+            });
+
+        if leads {
+            // among equal bids the earliest by `tie_break` leads
+            let tie_break = TIE_BREAK.may_load(deps.storage)?.unwrap_or_default();
+            let mut next_highest: Option<(Addr, Uint128, (u64, u32))> = None;
+            for item in BIDS
+                .idx
+                .amount
+                .range(deps.storage, None, None, Order::Descending)
+            {
+                let (bidder, amount) = item?;
+                let position = BID_ORDER
+                    .load(deps.storage, bidder.clone())?
+                    .tie_position(&tie_break);
+                match &next_highest {
+                    Some((_, highest_bid, _)) if amount < *highest_bid => break,
+                    Some((_, _, earliest)) if position >= *earliest => {}
+                    _ => next_highest = Some((bidder, amount, position)),
+                }
+            }
+            match next_highest {
+                Some((highest_bidder, highest_bid, _)) => {
+                    HIGHEST_BIDDER.save(deps.storage, &highest_bidder, env.block.height)?;
+                    HIGHEST_BID.save(deps.storage, &highest_bid, env.block.height)?;
+                    resp = resp
+                        .add_attribute("highest_bidder", highest_bidder)
+                        .add_attribute("highest_bid", highest_bid.to_string());
+                }
+                None => {
+                    HIGHEST_BIDDER.remove(deps.storage, env.block.height)?;
+                    HIGHEST_BID.save(deps.storage, &Uint128::zero(), env.block.height)?;
+                }
+            }
+        }
+
+        Ok(resp)
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, StdError};
+use cosmwasm_std::{Coin, StdError, Timestamp};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,18 +9,141 @@ pub enum ContractError {
     #[error("commission part can be between [0-25]%")]
     InvalidCommissionPart,
 
+    #[error("commission tiers must have strictly increasing thresholds above 0")]
+    InvalidCommissionTiers,
+
+    #[error("commission maximum tokens cannot be lower than its minimum tokens")]
+    InvalidCommissionMaximum,
+
     #[error("Unauthorized - only {owner} can call it")]
     Unauthorized { owner: String },
 
     #[error("Owner of an item cannot bid on the item")]
     OwnerCannotBid,
 
+    #[error("Bidder is not allowed to bid on the item")]
+    BidderNotAllowed,
+
+    #[error("Bidder is blocked from bidding on the item")]
+    BidderBlocked,
+
     #[error("Bid rejected as current highest bid value is {highest_bid}")]
     BidRejected { highest_bid: Coin },
 
     #[error("Bid rejected as no {denom} tokens")]
     BidRejectedMissingTokensInDenom { denom: String },
 
+    #[error("Bid rejected as it does not cover the commission of {commission}")]
+    BidRejectedBelowCommission { commission: Coin },
+
+    #[error("{field} cannot be changed to the detriment of existing bidders")]
+    ConfigLocked { field: String },
+
+    #[error("Bond of {bond} has to be posted before bidding")]
+    BondRequired { bond: Coin },
+
+    #[error("Bond rejected as exactly {bond} has to be sent")]
+    BondRejected { bond: Coin },
+
+    #[error("No bond is required to bid on the item")]
+    BondNotRequired,
+
+    #[error("Bond has already been posted")]
+    BondAlreadyPosted,
+
+    #[error("No winner bond to settle")]
+    NoWinnerBond,
+
+    #[error("Bond amount must be positive")]
+    InvalidBondAmount,
+
+    #[error("Bond cannot be slashed once the escrow has been released")]
+    BondReleased,
+
+    #[error("Bond can be reclaimed once the escrow is released or the claim window is over")]
+    BondLocked,
+
+    #[error("Winner cannot retract the winning bid")]
+    WinnerCannotRetract,
+
+    #[error("Only the winner can call it")]
+    NotWinner,
+
+    #[error("Only the arbiter can call it")]
+    NotArbiter,
+
+    #[error("No winning bid is held in escrow")]
+    NoEscrow,
+
+    #[error("Escrow has already been settled")]
+    EscrowSettled,
+
+    #[error("Escrow is already disputed")]
+    EscrowDisputed,
+
+    #[error("Escrow is not disputed")]
+    EscrowNotDisputed,
+
+    #[error("Only the winner or the owner can dispute the escrow")]
+    NotEscrowParty,
+
+    #[error("Escrow can no longer be disputed as it could be released since {release_at}")]
+    EscrowExpired { release_at: Timestamp },
+
+    #[error("Escrow cannot be released before {release_at}")]
+    EscrowLocked { release_at: Timestamp },
+
+    #[error("Escrow has no timeout")]
+    NoEscrowTimeout,
+
+    #[error("Seller share has to be between [0-100]%")]
+    InvalidSellerShare,
+
+    #[error("Unclaimed funds cannot be swept before {claim_until}")]
+    ClaimWindowOpen { claim_until: Timestamp },
+
+    #[error("No claim window is configured")]
+    NoClaimWindow,
+
+    #[error("Only the owner or the treasury can sweep unclaimed funds")]
+    NotSweeper,
+
+    #[error("Minimum increment must be positive")]
+    InvalidMinIncrement,
+
+    #[error("Sender is not an approved operator of the bidder")]
+    NotOperator,
+
+    #[error("Operator approval expired")]
+    ApprovalExpired,
+
+    #[error("Operator spend limit exceeded, {remaining} remaining")]
+    SpendLimitExceeded { remaining: Coin },
+
+    #[error("No bid to reject")]
+    NoBid,
+
+    #[error("Batch epoch must be at least one block")]
+    InvalidBatchEpoch,
+
+    #[error("Auction is not a batch auction")]
+    NotBatchAuction,
+
+    #[error("Proxy bids are not supported in batch auctions")]
+    ProxyBidInBatch,
+
+    #[error("Batch bids are waiting for their epoch to be cleared")]
+    EpochPending,
+
+    #[error("Auction is paused")]
+    Paused,
+
+    #[error("Only the owner or the guardian can pause")]
+    NotPauser,
+
+    #[error("Unknown reply id {id}")]
+    UnknownReply { id: u64 },
+
     #[error("Bid closed")]
     BidClosed,
 
@@ -1,9 +1,11 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+};
 use error::ContractError;
-use msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 
 mod contract;
 mod state;
@@ -17,11 +19,11 @@ pub mod multitest;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    contract::instantiate(deps, info, msg)
+    contract::instantiate(deps, env, info, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -29,7 +31,49 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     use QueryMsg::*;
     match msg {
         HighestBid {} => to_binary(&contract::query::highest_bid(deps)?),
+        HighestBidAtHeight { height } => {
+            to_binary(&contract::query::highest_bid_at_height(deps, height)?)
+        }
         TotalBid { addr } => to_binary(&contract::query::total_bid(deps, addr)?),
+        SimulateBid { bidder, amount } => {
+            to_binary(&contract::query::simulate_bid(deps, bidder, amount)?)
+        }
+        Config {} => to_binary(&contract::query::config(deps)?),
+        Allowlist { start_after, limit } => {
+            to_binary(&contract::query::allowlist(deps, start_after, limit)?)
+        }
+        Blocklist { start_after, limit } => {
+            to_binary(&contract::query::blocklist(deps, start_after, limit)?)
+        }
+        Bond { addr } => to_binary(&contract::query::bond(deps, addr)?),
+        Escrow {} => to_binary(&contract::query::escrow(deps)?),
+        Unclaimed {} => to_binary(&contract::query::unclaimed(deps)?),
+        Stats {} => to_binary(&contract::query::stats(deps)?),
+        Hooks { start_after, limit } => {
+            to_binary(&contract::query::hooks(deps, start_after, limit)?)
+        }
+        BidderHook { addr } => to_binary(&contract::query::bidder_hook(deps, addr)?),
+        Operators {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&contract::query::operators(
+            deps,
+            bidder,
+            start_after,
+            limit,
+        )?),
+        Leaderboard {
+            start_after,
+            limit,
+            caller,
+        } => to_binary(&contract::query::leaderboard(
+            deps,
+            start_after,
+            limit,
+            caller,
+        )?),
+        Epoch { epoch } => to_binary(&contract::query::epoch(deps, epoch)?),
     }
 }
 
@@ -42,8 +86,61 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     use ExecuteMsg::*;
     match msg {
-        Bid {} => contract::execute::bid(deps, env, info),
+        Bid {} => contract::execute::bid(deps, env, info, None, false),
+        BidFor { bidder } => contract::execute::bid(deps, env, info, Some(bidder), false),
+        ProxyBid {} => contract::execute::bid(deps, env, info, None, true),
         Close {} => contract::execute::close(deps, env, info),
-        Retract { receiver } => contract::execute::retract(deps, info, receiver),
+        Retract { receiver, bidder } => {
+            contract::execute::retract(deps, env, info, receiver, bidder)
+        }
+        UpdateConfig {
+            item,
+            bid_denom,
+            commission,
+        } => contract::execute::update_config(deps, info, item, bid_denom, commission),
+        SetEligibility { eligibility } => {
+            contract::execute::set_eligibility(deps, info, eligibility)
+        }
+        AllowBidders { addresses } => contract::execute::allow_bidders(deps, info, addresses),
+        DisallowBidders { addresses } => contract::execute::disallow_bidders(deps, info, addresses),
+        BlockBidders { addresses } => contract::execute::block_bidders(deps, info, addresses),
+        UnblockBidders { addresses } => contract::execute::unblock_bidders(deps, info, addresses),
+        PostBond {} => contract::execute::post_bond(deps, info),
+        ReleaseBond {} => contract::execute::settle_bond(deps, env, info, false),
+        SlashBond {} => contract::execute::settle_bond(deps, env, info, true),
+        ConfirmDelivery {} => contract::execute::confirm_delivery(deps, info),
+        OpenDispute { reason } => contract::execute::open_dispute(deps, env, info, reason),
+        Resolve { seller_share } => contract::execute::resolve(deps, info, seller_share),
+        ReleaseEscrow {} => contract::execute::release_escrow(deps, env, info),
+        RefundBatch { limit } => contract::execute::refund_batch(deps, env, info, limit),
+        Sweep { limit } => contract::execute::sweep(deps, env, info, limit),
+        RejectBid { bidder } => contract::execute::reject_bid(deps, env, info, bidder),
+        AddHook { addr } => contract::execute::add_hook(deps, info, addr),
+        RemoveHook { addr } => contract::execute::remove_hook(deps, info, addr),
+        ApproveOperator {
+            operator,
+            spend_limit,
+            expires_at,
+        } => contract::execute::approve_operator(deps, info, operator, spend_limit, expires_at),
+        RevokeOperator { operator } => contract::execute::revoke_operator(deps, info, operator),
+        SetBidderHook { addr } => contract::execute::set_bidder_hook(deps, info, addr),
+        Crank {} => contract::execute::crank(deps, env, info),
+        Pause { scope } => contract::execute::pause(deps, info, scope),
+        Unpause {} => contract::execute::unpause(deps, info),
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    use SudoMsg::*;
+    match msg {
+        ForceClose {} => contract::execute::force_close(deps, env),
+        ForceCancel { limit } => contract::execute::force_cancel(deps, env, limit),
+        ChangeOwner { owner } => contract::execute::change_owner(deps, owner),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    contract::reply(deps, reply)
+}
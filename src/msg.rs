@@ -1,5 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Coin, Decimal, Uint128};
+use cosmwasm_std::{
+    to_binary, Coin, CosmosMsg, Decimal, StdResult, Timestamp, Uint128, Uint256, WasmMsg,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -8,6 +10,188 @@ pub struct InstantiateMsg {
     pub owner: Option<String>,
     pub commission_minimum_tokens: Uint128,
     pub commission_part: Decimal,
+    /// Brackets charged instead of `commission_part` for the tokens above their threshold.
+    #[serde(default)]
+    pub commission_tiers: Vec<CommissionTier>,
+    pub commission_maximum_tokens: Option<Uint128>,
+    #[serde(default)]
+    pub eligibility: BidderEligibility,
+    /// Refundable bond in `bid_denom` every bidder has to post before the first bid.
+    pub bond_amount: Option<Uint128>,
+    /// Enables escrow: the winning bid stays in the contract after close until the winner
+    /// confirms delivery, or the arbiter resolves a dispute opened by the winner or owner.
+    pub arbiter: Option<String>,
+    /// Seconds after close when an undisputed escrow can be released to the owner by anyone.
+    pub escrow_timeout: Option<u64>,
+    /// Seconds after close losers have to claim their funds before they can be swept.
+    pub claim_window: Option<u64>,
+    /// Receiver of swept funds, the owner if not set.
+    pub treasury: Option<String>,
+    /// Step a proxy bid is raised by over a competing bid, 1 token if not set.
+    pub min_increment: Option<Uint128>,
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// Collects bids in epochs of this many blocks, they lead only once their epoch is cleared
+    /// with `Crank`.
+    pub batch_epoch_blocks: Option<u64>,
+    /// Can pause the auction besides the owner.
+    pub guardian: Option<String>,
+}
+
+/// How a bid equal to the highest bid is handled. Bids are ordered by block height and
+/// transaction index, the earlier one wins the tie.
+#[cw_serde]
+#[derive(Default)]
+pub enum TieBreak {
+    /// Equal bids are rejected, a bid has to top the highest bid.
+    #[default]
+    Reject,
+    /// Equal bids are accepted, the bidder who reached the total first leads.
+    EarliestBid,
+    /// Equal bids are accepted, the bidder whose first bid was placed earliest leads.
+    EarliestFirstBid,
+}
+
+/// What is rejected besides bids while the auction is paused.
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseScope {
+    pub close: bool,
+    /// Covers `RefundBatch` and `Sweep` as well.
+    pub retract: bool,
+}
+
+/// Who is allowed to bid, apart from the owner who never is.
+#[cw_serde]
+#[derive(Default)]
+pub enum BidderEligibility {
+    #[default]
+    Anyone,
+    /// Only addresses added with `AllowBidders`.
+    Allowlist,
+    /// Eligibility is asked from an external registry with `RegistryQueryMsg::IsVerified`.
+    Registry { contract: String },
+}
+
+/// Query interface an external eligibility registry has to implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RegistryQueryMsg {
+    #[returns(IsVerifiedResponse)]
+    IsVerified { address: String },
+}
+
+#[cw_serde]
+pub struct IsVerifiedResponse {
+    pub verified: bool,
+}
+
+/// Notification sent to hook contracts, wrapped in `AuctionHookExecuteMsg`.
+#[cw_serde]
+pub enum AuctionHookMsg {
+    /// `previous` highest bidder was outbid by `new` with a total bid of `amount`.
+    Outbid {
+        previous: String,
+        new: String,
+        amount: Coin,
+    },
+    /// Bidding closed, `winner` and `amount` are not set if there were no bids.
+    Closed {
+        winner: Option<String>,
+        amount: Option<Coin>,
+    },
+}
+
+impl AuctionHookMsg {
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: to_binary(&AuctionHookExecuteMsg::AuctionHook(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// Execute interface a hook contract has to implement.
+#[cw_serde]
+pub enum AuctionHookExecuteMsg {
+    AuctionHook(AuctionHookMsg),
+}
+
+/// Commission bracket: tokens of a single bid above `above` are charged `part`.
+#[cw_serde]
+pub struct CommissionTier {
+    pub above: Uint128,
+    pub part: Decimal,
+}
+
+/// Complete commission schedule, see the `commission_*` fields of `InstantiateMsg`.
+#[cw_serde]
+pub struct CommissionParams {
+    pub part: Decimal,
+    pub minimum_tokens: Uint128,
+    #[serde(default)]
+    pub tiers: Vec<CommissionTier>,
+    #[serde(default)]
+    pub maximum_tokens: Option<Uint128>,
+}
+
+impl CommissionParams {
+    /// Commission for a single bid of `amount` tokens. Every bracket is charged its own part,
+    /// the sum is rounded up and then clamped between the minimum and maximum tokens.
+    pub fn commission(&self, amount: Uint128) -> StdResult<Uint128> {
+        let mut brackets = vec![(Uint128::zero(), self.part)];
+        brackets.extend(self.tiers.iter().map(|tier| (tier.above, tier.part)));
+
+        let mut scaled = Uint256::zero();
+        for (idx, (lower, part)) in brackets.iter().enumerate() {
+            if amount <= *lower {
+                break;
+            }
+            let upper = brackets
+                .get(idx + 1)
+                .map_or(amount, |(next, _)| amount.min(*next));
+            scaled += (upper - lower).full_mul(part.atomics());
+        }
+
+        let unit = Uint256::from(Decimal::one().atomics());
+        let mut commission = Uint128::try_from((scaled + unit - Uint256::one()) / unit)?;
+
+        commission = commission.max(self.minimum_tokens);
+        if let Some(maximum_tokens) = self.maximum_tokens {
+            commission = commission.min(maximum_tokens);
+        }
+
+        Ok(commission)
+    }
+
+    /// Part charged on the tokens right above `amount`.
+    fn marginal_part(&self, amount: Uint128) -> Decimal {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.above <= amount)
+            .map_or(self.part, |tier| tier.part)
+    }
+
+    /// Whether no bid would ever be charged more with these params than with `other`.
+    pub fn never_exceeds(&self, other: &CommissionParams) -> bool {
+        let maximum_ok = match (self.maximum_tokens, other.maximum_tokens) {
+            (_, None) => true,
+            (Some(maximum), Some(other_maximum)) => maximum <= other_maximum,
+            (None, Some(_)) => false,
+        };
+
+        // marginal parts are constant between thresholds, so comparing them at every
+        // threshold of both schedules covers all amounts
+        let parts_ok = std::iter::once(Uint128::zero())
+            .chain(self.tiers.iter().map(|tier| tier.above))
+            .chain(other.tiers.iter().map(|tier| tier.above))
+            .all(|amount| self.marginal_part(amount) <= other.marginal_part(amount));
+
+        maximum_ok && parts_ok && self.minimum_tokens <= other.minimum_tokens
+    }
 }
 
 #[cw_serde]
@@ -15,15 +199,195 @@ pub struct InstantiateMsg {
 pub enum QueryMsg {
     #[returns(HighestBidResponse)]
     HighestBid {},
+    /// Highest bid as it was at the end of the block at `height`.
+    #[returns(HighestBidResponse)]
+    HighestBidAtHeight { height: u64 },
     #[returns(TotalBidResponse)]
     TotalBid { addr: String },
+    #[returns(SimulateBidResponse)]
+    SimulateBid { bidder: String, amount: Uint128 },
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(AddressesResponse)]
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(AddressesResponse)]
+    Blocklist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(BondResponse)]
+    Bond { addr: String },
+    #[returns(EscrowResponse)]
+    Escrow {},
+    /// Total bids and bonds still refundable to everyone but the winner.
+    #[returns(UnclaimedResponse)]
+    Unclaimed {},
+    #[returns(StatsResponse)]
+    Stats {},
+    /// Hook contracts notified of every outbid and the close.
+    #[returns(AddressesResponse)]
+    Hooks {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(BidderHookResponse)]
+    BidderHook { addr: String },
+    /// Operators approved by `bidder`, expired approvals included.
+    #[returns(OperatorsResponse)]
+    Operators {
+        bidder: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Standing bids from the highest, equal bids share a rank. `caller` is ranked as well.
+    /// In batch auctions bidders are ranked at their total bid as of the last cleared epoch.
+    #[returns(LeaderboardResponse)]
+    Leaderboard {
+        start_after: Option<LeaderboardCursor>,
+        limit: Option<u32>,
+        caller: Option<String>,
+    },
+    /// Outcome of a cleared batch epoch.
+    #[returns(EpochResponse)]
+    Epoch { epoch: u64 },
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     Bid {},
+    /// Bids the sent tokens on behalf of `bidder`, who becomes the potential winner and the one
+    /// to retract. All bidder checks apply to `bidder` rather than the sender. No approval is
+    /// needed, but bids of an approved operator count against its spend limit and expiry.
+    BidFor {
+        bidder: String,
+    },
+    /// Commits the sent tokens, less commission, to a maximum the standing bid of the sender is
+    /// raised to by `min_increment` over competing bids. The unused part of the maximum is
+    /// refunded to the winner on close.
+    ProxyBid {},
     Close {},
-    Retract { receiver: Option<String> },
+    /// Anyone but the highest bidder can withdraw while the bid is open (the bond stays posted),
+    /// after close everyone but the winner gets the total bid and bond back.
+    /// An approved operator can retract for `bidder`, the refund goes to `receiver` or else to
+    /// `bidder`.
+    Retract {
+        receiver: Option<String>,
+        bidder: Option<String>,
+    },
+    /// Owner only. Once the first bid or bond is placed only changes that do not hurt bidders are
+    /// accepted: the item and denom are frozen and commission can only be lowered.
+    UpdateConfig {
+        item: Option<String>,
+        bid_denom: Option<String>,
+        commission: Option<CommissionParams>,
+    },
+    /// Owner only.
+    SetEligibility {
+        eligibility: BidderEligibility,
+    },
+    /// Owner only.
+    AllowBidders {
+        addresses: Vec<String>,
+    },
+    /// Owner only.
+    DisallowBidders {
+        addresses: Vec<String>,
+    },
+    /// Owner only. Blocked bidders cannot bid but can still retract their bids after close.
+    BlockBidders {
+        addresses: Vec<String>,
+    },
+    /// Owner only.
+    UnblockBidders {
+        addresses: Vec<String>,
+    },
+    /// Posts the participation bond, exactly `bond_amount` tokens have to be sent.
+    PostBond {},
+    /// Owner only. Returns the bond of the winner once the post-close obligation is met. The
+    /// winner can reclaim it as well once the escrow is released or the claim window is over.
+    ReleaseBond {},
+    /// Owner only. Transfers the bond of the winner who failed the post-close obligation to the
+    /// owner.
+    SlashBond {},
+    /// Winner only. Releases the escrowed winning bid to the owner.
+    ConfirmDelivery {},
+    /// Winner or owner only. Holds the escrowed winning bid until the arbiter resolves the
+    /// dispute.
+    OpenDispute {
+        reason: String,
+    },
+    /// Arbiter only. Sends `seller_share` of the disputed winning bid to the owner and the rest
+    /// back to the winner.
+    Resolve {
+        seller_share: Decimal,
+    },
+    /// Releases the undisputed escrow to the owner once `escrow_timeout` passed since close.
+    ReleaseEscrow {},
+    /// Anyone after close. Refunds up to `limit` losing bidders, can be repeated until all are
+    /// refunded.
+    RefundBatch {
+        limit: Option<u32>,
+    },
+    /// Owner or treasury only. Forfeits up to `limit` unclaimed bids and bonds to the treasury
+    /// once the claim window after close is over.
+    Sweep {
+        limit: Option<u32>,
+    },
+    /// Owner only. Refunds the bid of `bidder` while bidding is open, the next highest bid
+    /// takes the lead if it was the highest. The bond stays posted. In batch auctions the
+    /// highest bid cannot be rejected while bids are waiting for their epoch to be cleared.
+    RejectBid {
+        bidder: String,
+    },
+    /// Owner only. Registers a contract notified with `AuctionHookMsg` of every outbid and the
+    /// close.
+    AddHook {
+        addr: String,
+    },
+    /// Owner only.
+    RemoveHook {
+        addr: String,
+    },
+    /// Lets `operator` bid with `BidFor`, retract and redirect refunds for the sender. Bids the
+    /// operator places count against `spend_limit`, replacing an earlier approval resets it.
+    ApproveOperator {
+        operator: String,
+        spend_limit: Option<Uint128>,
+        expires_at: Option<Timestamp>,
+    },
+    RevokeOperator {
+        operator: String,
+    },
+    /// Sets, or clears if not given, the hook contract of the sender, notified when the sender
+    /// is outbid or wins on close.
+    SetBidderHook {
+        addr: Option<String>,
+    },
+    /// Clears the finished batch epochs, anyone can crank.
+    Crank {},
+    /// Owner or guardian only. Rejects bids, and close or retract as given by `scope`, until
+    /// unpaused. The owner pausing again replaces the scope, the guardian can only widen it.
+    Pause {
+        #[serde(default)]
+        scope: PauseScope,
+    },
+    /// Owner only.
+    Unpause {},
+}
+
+/// Chain governance control through the `sudo` entry point.
+#[cw_serde]
+pub enum SudoMsg {
+    /// Closes bidding as the owner would.
+    ForceClose {},
+    /// Closes bidding without a winner and refunds up to `limit` bidders and bond holders, the
+    /// rest can be refunded with `RefundBatch`.
+    ForceCancel { limit: Option<u32> },
+    /// Hands the auction over to `owner`, who cannot be bidding on it while bidding is open.
+    ChangeOwner { owner: String },
 }
 
 #[cw_serde]
@@ -40,6 +404,153 @@ pub struct HighestBidResponse {
 pub struct TotalBidResponse {
     pub bid_closed: bool,
     pub amount: Option<Coin>,
+    /// Bid and bond swept after the claim window.
+    pub forfeited: Option<Coin>,
+    /// Maximum of the proxy bid, only kept while the bidder is the highest bidder.
+    pub proxy_max: Option<Coin>,
+    /// Commission paid on all bids of the bidder, kept after refunds.
+    pub commission: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    pub unique_bidders: u64,
+    pub bid_count: u64,
+    /// Bids and bonds held by the contract, including the winning bid until it is paid out.
+    pub total_escrowed: Coin,
+    pub total_commission: Coin,
+    /// Bids and bonds returned to bidders, forfeited funds are not included.
+    pub total_refunded: Coin,
+    /// Winning bid paid to the owner on close or escrow settlement.
+    pub total_paid_to_seller: Coin,
+    pub first_bid_height: Option<u64>,
+    pub last_bid_height: Option<u64>,
+}
+
+#[cw_serde]
+pub struct UnclaimedResponse {
+    pub amount: Coin,
+    pub claim_until: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub item: String,
+    pub bid_denom: String,
+    pub owner: String,
+    pub commission_minimum_tokens: Uint128,
+    pub commission_part: Decimal,
+    pub commission_tiers: Vec<CommissionTier>,
+    pub commission_maximum_tokens: Option<Uint128>,
+    pub eligibility: BidderEligibility,
+    pub bond_amount: Option<Uint128>,
+    pub arbiter: Option<String>,
+    pub escrow_timeout: Option<u64>,
+    pub claim_window: Option<u64>,
+    pub treasury: Option<String>,
+    pub min_increment: Option<Uint128>,
+    pub tie_break: TieBreak,
+    pub batch_epoch_blocks: Option<u64>,
+    pub guardian: Option<String>,
+    /// Scope of the pause, if paused.
+    pub paused: Option<PauseScope>,
+}
+
+#[cw_serde]
+pub struct AddressesResponse {
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct OperatorResponse {
+    pub operator: String,
+    pub spend_limit: Option<Coin>,
+    pub spent: Coin,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<OperatorResponse>,
+}
+
+#[cw_serde]
+pub struct RankedBidResponse {
+    pub rank: u32,
+    pub bidder: String,
+    pub amount: Coin,
+}
+
+/// Last row of the previous leaderboard page, paging goes on even if the bidder has left.
+#[cw_serde]
+pub struct LeaderboardCursor {
+    pub bidder: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct LeaderboardResponse {
+    pub bids: Vec<RankedBidResponse>,
+    pub caller: Option<RankedBidResponse>,
+}
+
+#[cw_serde]
+pub struct EpochResponse {
+    pub epoch: u64,
+    /// Highest bidder once the epoch was cleared, possibly from an earlier epoch.
+    pub highest_bidder: Option<String>,
+    pub highest_bid: Option<Coin>,
+    /// Bids placed in the epoch, ranked after the tie-break.
+    pub ranking: Vec<RankedBidResponse>,
+}
+
+#[cw_serde]
+pub struct BidderHookResponse {
+    pub hook: Option<String>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct BondResponse {
+    pub required: Option<Coin>,
+    pub posted: Option<Coin>,
+}
+
+#[cw_serde]
+pub enum EscrowStatus {
+    Pending,
+    Disputed,
+    /// Released to the owner on confirmed delivery or timeout.
+    Released,
+    /// Split by the arbiter.
+    Resolved,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct EscrowResponse {
+    pub amount: Option<Coin>,
+    pub status: Option<EscrowStatus>,
+    pub release_at: Option<Timestamp>,
+    pub dispute: Option<DisputeResponse>,
+}
+
+#[cw_serde]
+pub struct DisputeResponse {
+    pub opened_by: String,
+    pub reason: String,
+    pub opened_at: Timestamp,
+    pub seller_amount: Option<Coin>,
+    pub buyer_amount: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct SimulateBidResponse {
+    pub commission: Coin,
+    pub total_bid: Coin,
+    pub accepted: bool,
+    pub rejection: Option<String>,
 }
 
 impl HighestBidResponse {
@@ -1,16 +1,23 @@
-use cosmwasm_std::{Addr, Coin, Decimal, StdResult};
+use cosmwasm_std::{Addr, Coin, Decimal, StdResult, Timestamp, Uint128};
 use cw_multi_test::{App, ContractWrapper, Executor};
 
 use crate::{
     error::ContractError,
     execute, instantiate,
-    msg::{ExecuteMsg, HighestBidResponse, InstantiateMsg, QueryMsg, TotalBidResponse},
-    query,
+    msg::{
+        AddressesResponse, BidderEligibility, BondResponse, CommissionParams, ConfigResponse,
+        EpochResponse, EscrowResponse, ExecuteMsg, HighestBidResponse, InstantiateMsg,
+        LeaderboardCursor, LeaderboardResponse, OperatorsResponse, PauseScope, QueryMsg,
+        RankedBidResponse, SimulateBidResponse, StatsResponse, SudoMsg, TieBreak, TotalBidResponse,
+        UnclaimedResponse,
+    },
+    query, reply, sudo,
 };
 
 #[cfg(test)]
 mod tests;
 
+#[derive(Debug)]
 pub struct BiddingContract(Addr);
 
 impl BiddingContract {
@@ -21,11 +28,14 @@ impl BiddingContract {
 
     #[track_caller]
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_reply(reply);
         app.store_code(Box::new(contract))
     }
 
     #[track_caller]
+    #[allow(clippy::too_many_arguments)]
     pub fn instantiate<'a>(
         app: &mut App,
         code_id: u64,
@@ -36,26 +46,48 @@ impl BiddingContract {
         owner: impl Into<Option<&'a Addr>>,
         commission_minimum_tokens: u128,
         commission_part: Decimal,
-    ) -> StdResult<BiddingContract> {
+    ) -> Result<BiddingContract, ContractError> {
         let owner = owner.into();
         let item = item.into();
         let bid_denom = bid_denom.into();
-        app.instantiate_contract(
+        Self::instantiate_with_msg(
+            app,
             code_id,
-            sender.clone(),
+            sender,
+            label,
             &InstantiateMsg {
                 owner: owner.map(Addr::to_string),
                 item,
                 bid_denom,
                 commission_minimum_tokens: commission_minimum_tokens.into(),
                 commission_part,
+                commission_tiers: vec![],
+                commission_maximum_tokens: None,
+                eligibility: BidderEligibility::Anyone,
+                bond_amount: None,
+                arbiter: None,
+                escrow_timeout: None,
+                claim_window: None,
+                treasury: None,
+                min_increment: None,
+                tie_break: TieBreak::Reject,
+                batch_epoch_blocks: None,
+                guardian: None,
             },
-            &[],
-            label,
-            None,
         )
-        .map_err(|err| err.downcast().unwrap())
-        .map(BiddingContract)
+    }
+
+    #[track_caller]
+    pub fn instantiate_with_msg(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+    ) -> Result<BiddingContract, ContractError> {
+        app.instantiate_contract(code_id, sender.clone(), msg, &[], label, None)
+            .map_err(|err| err.downcast().unwrap())
+            .map(BiddingContract)
     }
 
     #[track_caller]
@@ -64,6 +96,18 @@ impl BiddingContract {
             .query_wasm_smart(self.addr().clone(), &QueryMsg::HighestBid {})
     }
 
+    #[track_caller]
+    pub fn query_highest_bid_at_height(
+        &self,
+        app: &App,
+        height: u64,
+    ) -> StdResult<HighestBidResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().clone(),
+            &QueryMsg::HighestBidAtHeight { height },
+        )
+    }
+
     #[track_caller]
     pub fn query_total_bid(&self, app: &App, addr: &Addr) -> StdResult<TotalBidResponse> {
         app.wrap().query_wasm_smart(
@@ -74,6 +118,132 @@ impl BiddingContract {
         )
     }
 
+    #[track_caller]
+    pub fn query_config(&self, app: &App) -> StdResult<ConfigResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr().clone(), &QueryMsg::Config {})
+    }
+
+    #[track_caller]
+    pub fn query_allowlist(
+        &self,
+        app: &App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().to_string(),
+            &QueryMsg::Allowlist { start_after, limit },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_blocklist(
+        &self,
+        app: &App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AddressesResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().to_string(),
+            &QueryMsg::Blocklist { start_after, limit },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_bond(&self, app: &App, addr: &Addr) -> StdResult<BondResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().to_string(),
+            &QueryMsg::Bond {
+                addr: addr.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_escrow(&self, app: &App) -> StdResult<EscrowResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr().clone(), &QueryMsg::Escrow {})
+    }
+
+    #[track_caller]
+    pub fn query_stats(&self, app: &App) -> StdResult<StatsResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr().clone(), &QueryMsg::Stats {})
+    }
+
+    #[track_caller]
+    pub fn query_unclaimed(&self, app: &App) -> StdResult<UnclaimedResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr().clone(), &QueryMsg::Unclaimed {})
+    }
+
+    #[track_caller]
+    pub fn query_hooks(&self, app: &App) -> StdResult<AddressesResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().clone(),
+            &QueryMsg::Hooks {
+                start_after: None,
+                limit: None,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_operators(&self, app: &App, bidder: &Addr) -> StdResult<OperatorsResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().clone(),
+            &QueryMsg::Operators {
+                bidder: bidder.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_leaderboard(
+        &self,
+        app: &App,
+        start_after: Option<&RankedBidResponse>,
+        limit: Option<u32>,
+        caller: Option<&Addr>,
+    ) -> StdResult<LeaderboardResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().clone(),
+            &QueryMsg::Leaderboard {
+                start_after: start_after.map(|row| LeaderboardCursor {
+                    bidder: row.bidder.clone(),
+                    amount: row.amount.amount,
+                }),
+                limit,
+                caller: caller.map(Addr::to_string),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_epoch(&self, app: &App, epoch: u64) -> StdResult<EpochResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr().clone(), &QueryMsg::Epoch { epoch })
+    }
+
+    #[track_caller]
+    pub fn query_simulate_bid(
+        &self,
+        app: &App,
+        bidder: &Addr,
+        amount: u128,
+    ) -> StdResult<SimulateBidResponse> {
+        app.wrap().query_wasm_smart(
+            self.addr().to_string(),
+            &QueryMsg::SimulateBid {
+                bidder: bidder.to_string(),
+                amount: Uint128::new(amount),
+            },
+        )
+    }
+
     #[track_caller]
     pub fn bid(&self, app: &mut App, bidder: &Addr, tokens: Coin) -> Result<(), ContractError> {
         app.execute_contract(
@@ -86,6 +256,43 @@ impl BiddingContract {
         .map(|_| ())
     }
 
+    #[track_caller]
+    pub fn bid_for(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        bidder: &Addr,
+        tokens: Coin,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::BidFor {
+                bidder: bidder.to_string(),
+            },
+            &[tokens],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn proxy_bid(
+        &self,
+        app: &mut App,
+        bidder: &Addr,
+        tokens: Coin,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            bidder.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::ProxyBid {},
+            &[tokens],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
     #[track_caller]
     pub fn close(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
         app.execute_contract(
@@ -108,7 +315,443 @@ impl BiddingContract {
         app.execute_contract(
             sender.clone(),
             self.addr().clone(),
-            &ExecuteMsg::Retract { receiver },
+            &ExecuteMsg::Retract {
+                receiver,
+                bidder: None,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn retract_for(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        bidder: &Addr,
+        receiver: Option<String>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Retract {
+                receiver,
+                bidder: Some(bidder.to_string()),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn approve_operator(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operator: &Addr,
+        spend_limit: Option<u128>,
+        expires_at: Option<Timestamp>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::ApproveOperator {
+                operator: operator.to_string(),
+                spend_limit: spend_limit.map(Uint128::new),
+                expires_at,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn revoke_operator(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operator: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::RevokeOperator {
+                operator: operator.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        item: Option<String>,
+        bid_denom: Option<String>,
+        commission: Option<CommissionParams>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::UpdateConfig {
+                item,
+                bid_denom,
+                commission,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn set_eligibility(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        eligibility: BidderEligibility,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::SetEligibility { eligibility },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn allow_bidders(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        addresses: &[&Addr],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::AllowBidders {
+                addresses: addresses.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn disallow_bidders(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        addresses: &[&Addr],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::DisallowBidders {
+                addresses: addresses.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn block_bidders(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        addresses: &[&Addr],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::BlockBidders {
+                addresses: addresses.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn unblock_bidders(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        addresses: &[&Addr],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::UnblockBidders {
+                addresses: addresses.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn post_bond(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        tokens: Coin,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::PostBond {},
+            &[tokens],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn release_bond(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::ReleaseBond {},
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn slash_bond(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::SlashBond {},
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn confirm_delivery(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::ConfirmDelivery {},
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn open_dispute(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        reason: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::OpenDispute {
+                reason: reason.to_owned(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn resolve(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        seller_share: Decimal,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Resolve { seller_share },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn refund_batch(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        limit: Option<u32>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::RefundBatch { limit },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn sweep(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        limit: Option<u32>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Sweep { limit },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn sudo(&self, app: &mut App, msg: &SudoMsg) -> Result<(), ContractError> {
+        app.wasm_sudo(self.addr().clone(), msg)
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+            .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn pause(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        scope: PauseScope,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Pause { scope },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn unpause(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Unpause {},
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn crank(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::Crank {},
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn reject_bid(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        bidder: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::RejectBid {
+                bidder: bidder.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn add_hook(&self, app: &mut App, sender: &Addr, hook: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::AddHook {
+                addr: hook.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn remove_hook(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hook: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::RemoveHook {
+                addr: hook.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn set_bidder_hook(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hook: Option<&Addr>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::SetBidderHook {
+                addr: hook.map(Addr::to_string),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn release_escrow(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.addr().clone(),
+            &ExecuteMsg::ReleaseEscrow {},
             &[],
         )
         .map_err(|err| err.downcast::<ContractError>().unwrap())
@@ -1,7 +1,23 @@
-use cosmwasm_std::{coin, coins, Addr, Decimal};
-use cw_multi_test::App;
+use cosmwasm_std::{
+    attr, coin, coins,
+    testing::{mock_dependencies, mock_env, mock_info},
+    to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    Reply, ReplyOn, Response, StdError, StdResult, SubMsgResult, Uint128, WasmMsg,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
 
-use crate::{error::ContractError, msg::HighestBidResponse};
+use crate::{
+    contract::HOOK_GAS_LIMIT,
+    error::ContractError,
+    msg::{
+        AddressesResponse, AuctionHookExecuteMsg, AuctionHookMsg, BidderEligibility, BondResponse,
+        CommissionParams, CommissionTier, EpochResponse, EscrowResponse, EscrowStatus, ExecuteMsg,
+        HighestBidResponse, InstantiateMsg, IsVerifiedResponse, LeaderboardResponse,
+        OperatorResponse, PauseScope, RankedBidResponse, RegistryQueryMsg, SimulateBidResponse,
+        StatsResponse, SudoMsg, TieBreak, UnclaimedResponse,
+    },
+};
 
 use super::BiddingContract;
 
@@ -184,6 +200,14 @@ fn bid_with_commission_flow() {
         app.wrap().query_balance(&owner, ATOM).unwrap(),
         coin(2, ATOM)
     );
+    assert_eq!(
+        contract.query_stats(&app).unwrap().total_commission,
+        coin(2, ATOM)
+    );
+    assert_eq!(
+        contract.query_total_bid(&app, &alex).unwrap().commission,
+        Some(coin(1, ATOM))
+    );
 
     contract.close(&mut app, &owner).unwrap();
 
@@ -214,6 +238,21 @@ fn bid_with_commission_flow() {
         app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
         coin(0, ATOM)
     );
+
+    let height = app.block_info().height;
+    assert_eq!(
+        contract.query_stats(&app).unwrap(),
+        StatsResponse {
+            unique_bidders: 2,
+            bid_count: 2,
+            total_escrowed: coin(0, ATOM),
+            total_commission: coin(2, ATOM),
+            total_refunded: coin(14, ATOM),
+            total_paid_to_seller: coin(16, ATOM),
+            first_bid_height: Some(height),
+            last_bid_height: Some(height),
+        }
+    );
 }
 
 #[test]
@@ -249,11 +288,12 @@ fn owner_cannot_bid() {
 #[test]
 fn open_bid_cannot_retract() {
     let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &owner, coins(10, ATOM))
+            .init_balance(storage, &alex, coins(10, ATOM))
             .unwrap();
     });
 
@@ -272,6 +312,2710 @@ fn open_bid_cannot_retract() {
     )
     .unwrap();
 
-    let err = contract.retract(&mut app, &owner, None).unwrap_err();
+    contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap();
+    let err = contract.retract(&mut app, &alex, None).unwrap_err();
     assert_eq!(err, ContractError::BidOpen {});
 }
+
+#[test]
+fn outbid_bidder_withdraws_while_open() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let friend = Addr::unchecked("friend");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(2)),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.post_bond(&mut app, &alex, coin(2, ATOM)).unwrap();
+    contract.post_bond(&mut app, &ann, coin(2, ATOM)).unwrap();
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    contract
+        .retract(&mut app, &alex, Some(friend.to_string()))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&friend, ATOM).unwrap(),
+        coin(10, ATOM)
+    );
+    assert_eq!(contract.query_total_bid(&app, &alex).unwrap().amount, None);
+    assert_eq!(
+        contract.query_bond(&app, &alex).unwrap().posted,
+        Some(coin(2, ATOM))
+    );
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&ann, coin(12, ATOM))
+    );
+
+    let err = contract.bid(&mut app, &alex, coin(12, ATOM)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidRejected {
+            highest_bid: coin(12, ATOM)
+        }
+    );
+    contract.bid(&mut app, &alex, coin(13, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(13, ATOM))
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    contract.retract(&mut app, &ann, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+}
+
+#[test]
+fn simulate_bid_matches_bid() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let contract = BiddingContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        ANTIQUE_ITEM,
+        ATOM,
+        None,
+        2,
+        Decimal::percent(0),
+    )
+    .unwrap();
+
+    assert_eq!(
+        contract.query_simulate_bid(&app, &alex, 15).unwrap(),
+        SimulateBidResponse {
+            commission: coin(2, ATOM),
+            total_bid: coin(13, ATOM),
+            accepted: true,
+            rejection: None,
+        }
+    );
+
+    contract.bid(&mut app, &alex, coin(15, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_total_bid(&app, &alex).unwrap().amount,
+        Some(coin(13, ATOM))
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(2, ATOM)
+    );
+
+    let simulation = contract.query_simulate_bid(&app, &ann, 10).unwrap();
+    assert_eq!(simulation.commission, coin(2, ATOM));
+    assert_eq!(simulation.total_bid, coin(8, ATOM));
+    assert!(!simulation.accepted);
+    assert_eq!(
+        simulation.rejection,
+        Some(
+            ContractError::BidRejected {
+                highest_bid: coin(13, ATOM)
+            }
+            .to_string()
+        )
+    );
+
+    let simulation = contract.query_simulate_bid(&app, &ann, 1).unwrap();
+    assert_eq!(
+        simulation.rejection,
+        Some(
+            ContractError::BidRejectedBelowCommission {
+                commission: coin(2, ATOM)
+            }
+            .to_string()
+        )
+    );
+
+    let simulation = contract.query_simulate_bid(&app, &owner, 20).unwrap();
+    assert_eq!(
+        simulation.rejection,
+        Some(ContractError::OwnerCannotBid.to_string())
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    let simulation = contract.query_simulate_bid(&app, &ann, 20).unwrap();
+    assert_eq!(
+        simulation.rejection,
+        Some(ContractError::BidClosed.to_string())
+    );
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        item: ANTIQUE_ITEM.to_owned(),
+        bid_denom: ATOM.to_owned(),
+        owner: None,
+        commission_minimum_tokens: Uint128::zero(),
+        commission_part: Decimal::percent(0),
+        commission_tiers: vec![],
+        commission_maximum_tokens: None,
+        eligibility: BidderEligibility::Anyone,
+        bond_amount: None,
+        arbiter: None,
+        escrow_timeout: None,
+        claim_window: None,
+        treasury: None,
+        min_increment: None,
+        tie_break: TieBreak::Reject,
+        batch_epoch_blocks: None,
+        guardian: None,
+    }
+}
+
+#[test]
+fn tiered_commission_flow() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(2000, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(3000, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let msg = InstantiateMsg {
+        commission_part: Decimal::percent(5),
+        commission_tiers: vec![CommissionTier {
+            above: Uint128::new(1000),
+            part: Decimal::percent(2),
+        }],
+        commission_maximum_tokens: Some(Uint128::new(70)),
+        ..instantiate_msg()
+    };
+    let contract =
+        BiddingContract::instantiate_with_msg(&mut app, code_id, &owner, BIDDING_CONTRACT, &msg)
+            .unwrap();
+
+    let config = contract.query_config(&app).unwrap();
+    assert_eq!(config.owner, owner.to_string());
+    assert_eq!(config.commission_part, Decimal::percent(5));
+    assert_eq!(config.commission_tiers, msg.commission_tiers);
+    assert_eq!(config.commission_maximum_tokens, Some(Uint128::new(70)));
+
+    // 5% of 1000 + 2% of 500
+    contract.bid(&mut app, &alex, coin(1500, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(1440, ATOM))
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(60, ATOM)
+    );
+
+    // 5% of 1000 + 2% of 2000 is capped at 70
+    contract.bid(&mut app, &ann, coin(3000, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&ann, coin(2930, ATOM))
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(130, ATOM)
+    );
+
+    // 5% of 15 rounded up
+    assert_eq!(
+        contract
+            .query_simulate_bid(&app, &alex, 15)
+            .unwrap()
+            .commission,
+        coin(1, ATOM)
+    );
+}
+
+#[test]
+fn invalid_commission_schedule() {
+    let owner = Addr::unchecked("owner");
+    let mut app = App::default();
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            commission_part: Decimal::percent(26),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCommissionPart);
+
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            commission_tiers: vec![CommissionTier {
+                above: Uint128::new(1000),
+                part: Decimal::percent(30),
+            }],
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCommissionPart);
+
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            commission_tiers: vec![
+                CommissionTier {
+                    above: Uint128::new(1000),
+                    part: Decimal::percent(2),
+                },
+                CommissionTier {
+                    above: Uint128::new(500),
+                    part: Decimal::percent(1),
+                },
+            ],
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCommissionTiers);
+
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            commission_minimum_tokens: Uint128::new(5),
+            commission_maximum_tokens: Some(Uint128::new(4)),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCommissionMaximum);
+}
+
+#[test]
+fn update_config_before_and_after_first_bid() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(100, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = contract
+        .update_config(&mut app, &alex, Some("fake item".to_owned()), None, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    let commission = CommissionParams {
+        part: Decimal::percent(10),
+        minimum_tokens: Uint128::new(1),
+        tiers: vec![],
+        maximum_tokens: None,
+    };
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            Some("vintage item".to_owned()),
+            None,
+            Some(commission.clone()),
+        )
+        .unwrap();
+
+    let config = contract.query_config(&app).unwrap();
+    assert_eq!(config.item, "vintage item");
+    assert_eq!(config.commission_part, Decimal::percent(10));
+    assert_eq!(config.commission_minimum_tokens, Uint128::new(1));
+
+    contract.bid(&mut app, &alex, coin(50, ATOM)).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(5, ATOM)
+    );
+
+    let err = contract
+        .update_config(&mut app, &owner, Some("fake item".to_owned()), None, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConfigLocked {
+            field: "item".to_owned()
+        }
+    );
+
+    let err = contract
+        .update_config(&mut app, &owner, None, Some("osmo".to_owned()), None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConfigLocked {
+            field: "bid_denom".to_owned()
+        }
+    );
+
+    // more expensive above 100 tokens
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            None,
+            None,
+            Some(CommissionParams {
+                part: Decimal::percent(5),
+                tiers: vec![CommissionTier {
+                    above: Uint128::new(100),
+                    part: Decimal::percent(20),
+                }],
+                ..commission.clone()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConfigLocked {
+            field: "commission".to_owned()
+        }
+    );
+
+    contract
+        .update_config(
+            &mut app,
+            &owner,
+            None,
+            None,
+            Some(CommissionParams {
+                part: Decimal::percent(5),
+                tiers: vec![CommissionTier {
+                    above: Uint128::new(100),
+                    part: Decimal::percent(2),
+                }],
+                maximum_tokens: Some(Uint128::new(10)),
+                ..commission
+            }),
+        )
+        .unwrap();
+    assert_eq!(
+        contract.query_config(&app).unwrap().commission_part,
+        Decimal::percent(5)
+    );
+}
+
+#[test]
+fn allowlist_gates_bidders() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            eligibility: BidderEligibility::Allowlist,
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    let err = contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap_err();
+    assert_eq!(err, ContractError::BidderNotAllowed);
+
+    let err = contract
+        .allow_bidders(&mut app, &alex, &[&alex])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract
+        .allow_bidders(&mut app, &owner, &[&alex, &ann])
+        .unwrap();
+    assert_eq!(
+        contract.query_allowlist(&app, None, None).unwrap(),
+        AddressesResponse {
+            addresses: vec![alex.to_string(), ann.to_string()]
+        }
+    );
+    assert_eq!(
+        contract
+            .query_allowlist(&app, Some(alex.to_string()), Some(1))
+            .unwrap(),
+        AddressesResponse {
+            addresses: vec![ann.to_string()]
+        }
+    );
+
+    contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap();
+
+    contract
+        .disallow_bidders(&mut app, &owner, &[&ann])
+        .unwrap();
+    let err = contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap_err();
+    assert_eq!(err, ContractError::BidderNotAllowed);
+
+    contract
+        .set_eligibility(&mut app, &owner, BidderEligibility::Anyone)
+        .unwrap();
+    contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&ann, coin(10, ATOM))
+    );
+}
+
+fn registry_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn registry_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn registry_query(_: Deps, _: Env, msg: RegistryQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        RegistryQueryMsg::IsVerified { address } => to_binary(&IsVerifiedResponse {
+            verified: address == "ann",
+        }),
+    }
+}
+
+#[test]
+fn registry_gates_bidders() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let registry_code_id = app.store_code(Box::new(ContractWrapper::new(
+        registry_execute,
+        registry_instantiate,
+        registry_query,
+    )));
+    let registry = app
+        .instantiate_contract(
+            registry_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap();
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            eligibility: BidderEligibility::Registry {
+                contract: registry.to_string(),
+            },
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        contract.query_config(&app).unwrap().eligibility,
+        BidderEligibility::Registry {
+            contract: registry.to_string()
+        }
+    );
+
+    let err = contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap_err();
+    assert_eq!(err, ContractError::BidderNotAllowed);
+
+    contract.bid(&mut app, &ann, coin(5, ATOM)).unwrap();
+}
+
+#[test]
+fn blocked_bidder_cannot_bid_but_can_retract() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let shill = Addr::unchecked("shill");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+
+    let err = contract
+        .block_bidders(&mut app, &ann, &[&alex])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract
+        .block_bidders(&mut app, &owner, &[&shill, &alex])
+        .unwrap();
+    assert_eq!(
+        contract.query_blocklist(&app, None, Some(1)).unwrap(),
+        AddressesResponse {
+            addresses: vec![alex.to_string()]
+        }
+    );
+    assert_eq!(
+        contract
+            .query_blocklist(&app, Some(alex.to_string()), None)
+            .unwrap(),
+        AddressesResponse {
+            addresses: vec![shill.to_string()]
+        }
+    );
+
+    let err = contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap_err();
+    assert_eq!(err, ContractError::BidderBlocked);
+
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+
+    contract
+        .unblock_bidders(&mut app, &owner, &[&alex])
+        .unwrap();
+    assert_eq!(
+        contract.query_blocklist(&app, None, None).unwrap(),
+        AddressesResponse {
+            addresses: vec![shill.to_string()]
+        }
+    );
+}
+
+#[test]
+fn bond_flow() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &carl, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::zero()),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBondAmount);
+
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(5)),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    let err = contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BondRequired {
+            bond: coin(5, ATOM)
+        }
+    );
+
+    let err = contract
+        .post_bond(&mut app, &alex, coin(4, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BondRejected {
+            bond: coin(5, ATOM)
+        }
+    );
+
+    contract.post_bond(&mut app, &alex, coin(5, ATOM)).unwrap();
+    let err = contract
+        .post_bond(&mut app, &alex, coin(5, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::BondAlreadyPosted);
+
+    let err = contract
+        .update_config(&mut app, &owner, None, Some("eth".to_owned()), None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConfigLocked {
+            field: "bid_denom".to_owned()
+        }
+    );
+    assert_eq!(
+        contract.query_bond(&app, &alex).unwrap(),
+        BondResponse {
+            required: Some(coin(5, ATOM)),
+            posted: Some(coin(5, ATOM)),
+        }
+    );
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.post_bond(&mut app, &ann, coin(5, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    contract.post_bond(&mut app, &carl, coin(5, ATOM)).unwrap();
+
+    let err = contract.release_bond(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::BidOpen);
+
+    contract.close(&mut app, &owner).unwrap();
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+
+    contract.retract(&mut app, &carl, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&carl, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+
+    let err = contract.retract(&mut app, &ann, None).unwrap_err();
+    assert_eq!(err, ContractError::WinnerCannotRetract);
+
+    let err = contract.slash_bond(&mut app, &ann).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract.slash_bond(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(17, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+
+    let err = contract.release_bond(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::NoWinnerBond);
+}
+
+#[test]
+fn bond_cannot_be_slashed_after_escrow_release() {
+    let owner = Addr::unchecked("owner");
+    let arbiter = Addr::unchecked("arbiter");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(5)),
+            arbiter: Some(arbiter.to_string()),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.post_bond(&mut app, &ann, coin(5, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+    let err = contract.release_bond(&mut app, &ann).unwrap_err();
+    assert_eq!(err, ContractError::BondLocked);
+    contract.confirm_delivery(&mut app, &ann).unwrap();
+
+    let err = contract.slash_bond(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::BondReleased);
+
+    // the winner does not wait for the owner
+    contract.release_bond(&mut app, &ann).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(10, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(15, ATOM)
+    );
+}
+
+#[test]
+fn winner_reclaims_bond_after_claim_window() {
+    let owner = Addr::unchecked("owner");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(5)),
+            claim_window: Some(1000),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.post_bond(&mut app, &ann, coin(5, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    let err = contract.release_bond(&mut app, &ann).unwrap_err();
+    assert_eq!(err, ContractError::BondLocked);
+    let err = contract.slash_bond(&mut app, &ann).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(1000));
+    contract.release_bond(&mut app, &ann).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(10, ATOM)
+    );
+    let err = contract.slash_bond(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::NoWinnerBond);
+}
+
+#[test]
+fn escrow_released_on_delivery() {
+    let owner = Addr::unchecked("owner");
+    let arbiter = Addr::unchecked("arbiter");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &alex, coins(25, ATOM))
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            arbiter: Some(arbiter.to_string()),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+
+    let err = contract.confirm_delivery(&mut app, &ann).unwrap_err();
+    assert_eq!(err, ContractError::NoEscrow);
+
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+    assert_eq!(
+        contract.query_escrow(&app).unwrap(),
+        EscrowResponse {
+            amount: Some(coin(15, ATOM)),
+            status: Some(EscrowStatus::Pending),
+            release_at: None,
+            dispute: None,
+        }
+    );
+
+    let err = contract.confirm_delivery(&mut app, &alex).unwrap_err();
+    assert_eq!(err, ContractError::NotWinner);
+
+    contract.confirm_delivery(&mut app, &ann).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(15, ATOM)
+    );
+    assert_eq!(
+        contract.query_escrow(&app).unwrap().status,
+        Some(EscrowStatus::Released)
+    );
+
+    let err = contract
+        .open_dispute(&mut app, &ann, "damaged item")
+        .unwrap_err();
+    assert_eq!(err, ContractError::EscrowSettled);
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+}
+
+#[test]
+fn escrow_dispute_split_by_arbiter() {
+    let owner = Addr::unchecked("owner");
+    let arbiter = Addr::unchecked("arbiter");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            arbiter: Some(arbiter.to_string()),
+            escrow_timeout: Some(100),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    let err = contract
+        .resolve(&mut app, &arbiter, Decimal::percent(50))
+        .unwrap_err();
+    assert_eq!(err, ContractError::EscrowNotDisputed);
+
+    let err = contract
+        .open_dispute(&mut app, &alex, "damaged item")
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotEscrowParty);
+
+    contract
+        .open_dispute(&mut app, &ann, "damaged item")
+        .unwrap();
+    let err = contract
+        .open_dispute(&mut app, &owner, "item was fine")
+        .unwrap_err();
+    assert_eq!(err, ContractError::EscrowDisputed);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    let err = contract.release_escrow(&mut app, &alex).unwrap_err();
+    assert_eq!(err, ContractError::EscrowDisputed);
+
+    let err = contract
+        .resolve(&mut app, &owner, Decimal::percent(50))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotArbiter);
+
+    let err = contract
+        .resolve(&mut app, &arbiter, Decimal::percent(101))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSellerShare);
+
+    contract
+        .resolve(&mut app, &arbiter, Decimal::percent(40))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(19, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(6, ATOM)
+    );
+
+    let escrow = contract.query_escrow(&app).unwrap();
+    assert_eq!(escrow.status, Some(EscrowStatus::Resolved));
+    let dispute = escrow.dispute.unwrap();
+    assert_eq!(dispute.opened_by, ann.to_string());
+    assert_eq!(dispute.reason, "damaged item");
+    assert_eq!(dispute.seller_amount, Some(coin(6, ATOM)));
+    assert_eq!(dispute.buyer_amount, Some(coin(9, ATOM)));
+}
+
+#[test]
+fn undisputed_escrow_released_after_timeout() {
+    let owner = Addr::unchecked("owner");
+    let arbiter = Addr::unchecked("arbiter");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &ann, coins(25, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            arbiter: Some(arbiter.to_string()),
+            escrow_timeout: Some(100),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    let release_at = app.block_info().time.plus_seconds(100);
+    assert_eq!(
+        contract.query_escrow(&app).unwrap().release_at,
+        Some(release_at)
+    );
+
+    let err = contract.release_escrow(&mut app, &alex).unwrap_err();
+    assert_eq!(err, ContractError::EscrowLocked { release_at });
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let err = contract
+        .open_dispute(&mut app, &ann, "late complaint")
+        .unwrap_err();
+    assert_eq!(err, ContractError::EscrowExpired { release_at });
+
+    contract.release_escrow(&mut app, &alex).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(15, ATOM)
+    );
+    assert_eq!(
+        contract.query_escrow(&app).unwrap().status,
+        Some(EscrowStatus::Released)
+    );
+}
+
+#[test]
+fn refund_batches_alongside_retract() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+    let dave = Addr::unchecked("dave");
+    let eve = Addr::unchecked("eve");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl, &dave, &eve] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(1)),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    for (idx, bidder) in [&alex, &ann, &carl, &dave].into_iter().enumerate() {
+        contract.post_bond(&mut app, bidder, coin(1, ATOM)).unwrap();
+        contract
+            .bid(&mut app, bidder, coin(10 + idx as u128, ATOM))
+            .unwrap();
+    }
+    contract.post_bond(&mut app, &eve, coin(1, ATOM)).unwrap();
+
+    let err = contract.refund_batch(&mut app, &eve, None).unwrap_err();
+    assert_eq!(err, ContractError::BidOpen);
+
+    contract.close(&mut app, &owner).unwrap();
+
+    contract.refund_batch(&mut app, &owner, Some(2)).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&carl, ATOM).unwrap(),
+        coin(12, ATOM)
+    );
+
+    contract.retract(&mut app, &carl, None).unwrap();
+    contract.refund_batch(&mut app, &owner, Some(2)).unwrap();
+    contract.refund_batch(&mut app, &owner, None).unwrap();
+
+    for bidder in [&alex, &ann, &carl, &eve] {
+        assert_eq!(
+            app.wrap().query_balance(bidder, ATOM).unwrap(),
+            coin(25, ATOM)
+        );
+    }
+    assert_eq!(
+        app.wrap().query_balance(&dave, ATOM).unwrap(),
+        coin(11, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(1, ATOM)
+    );
+    assert_eq!(
+        contract.query_bond(&app, &dave).unwrap().posted,
+        Some(coin(1, ATOM))
+    );
+}
+
+#[test]
+fn unclaimed_funds_swept_after_claim_window() {
+    let owner = Addr::unchecked("owner");
+    let treasury = Addr::unchecked("treasury");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            bond_amount: Some(Uint128::new(1)),
+            claim_window: Some(1000),
+            treasury: Some(treasury.to_string()),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    for (bidder, amount) in [(&alex, 10), (&ann, 11), (&carl, 12)] {
+        contract.post_bond(&mut app, bidder, coin(1, ATOM)).unwrap();
+        contract.bid(&mut app, bidder, coin(amount, ATOM)).unwrap();
+    }
+    assert_eq!(
+        contract.query_unclaimed(&app).unwrap(),
+        UnclaimedResponse {
+            amount: coin(23, ATOM),
+            claim_until: None,
+        }
+    );
+
+    let err = contract.sweep(&mut app, &owner, None).unwrap_err();
+    assert_eq!(err, ContractError::BidOpen);
+
+    contract.close(&mut app, &owner).unwrap();
+    let claim_until = app.block_info().time.plus_seconds(1000);
+    assert_eq!(
+        contract.query_unclaimed(&app).unwrap(),
+        UnclaimedResponse {
+            amount: coin(23, ATOM),
+            claim_until: Some(claim_until),
+        }
+    );
+
+    let err = contract.sweep(&mut app, &treasury, None).unwrap_err();
+    assert_eq!(err, ContractError::ClaimWindowOpen { claim_until });
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        contract.query_unclaimed(&app).unwrap().amount,
+        coin(12, ATOM)
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(1000));
+    let err = contract.sweep(&mut app, &ann, None).unwrap_err();
+    assert_eq!(err, ContractError::NotSweeper);
+
+    contract.sweep(&mut app, &treasury, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&treasury, ATOM).unwrap(),
+        coin(12, ATOM)
+    );
+    assert_eq!(
+        contract.query_unclaimed(&app).unwrap().amount,
+        coin(0, ATOM)
+    );
+
+    let total_bid = contract.query_total_bid(&app, &ann).unwrap();
+    assert_eq!(total_bid.amount, None);
+    assert_eq!(total_bid.forfeited, Some(coin(12, ATOM)));
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+
+    // only the bond of the winner is left
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(1, ATOM)
+    );
+}
+
+#[test]
+fn auction_events_emitted() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            commission_part: Decimal::percent(10),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+    let auction_id = contract.addr().to_string();
+
+    let resp = app
+        .execute_contract(
+            alex.clone(),
+            contract.addr().clone(),
+            &ExecuteMsg::Bid {},
+            &coins(10, ATOM),
+        )
+        .unwrap();
+    let event = Event::new("wasm-auction_bid")
+        .add_attribute("auction_id", &auction_id)
+        .add_attribute("bidder", alex.as_str())
+        .add_attribute("amount", "10")
+        .add_attribute("commission", "1")
+        .add_attribute("total_bid", "9");
+    resp.assert_event(&event);
+    assert!(!resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "previous_highest_bidder")));
+
+    let resp = app
+        .execute_contract(
+            ann.clone(),
+            contract.addr().clone(),
+            &ExecuteMsg::Bid {},
+            &coins(20, ATOM),
+        )
+        .unwrap();
+    let event = Event::new("wasm-auction_bid")
+        .add_attribute("auction_id", &auction_id)
+        .add_attribute("bidder", ann.as_str())
+        .add_attribute("commission", "2")
+        .add_attribute("total_bid", "18")
+        .add_attribute("previous_highest_bidder", alex.as_str());
+    resp.assert_event(&event);
+
+    let resp = app
+        .execute_contract(
+            owner.clone(),
+            contract.addr().clone(),
+            &ExecuteMsg::Close {},
+            &[],
+        )
+        .unwrap();
+    let event = Event::new("wasm-auction_closed")
+        .add_attribute("auction_id", &auction_id)
+        .add_attribute("closing_bid", "18")
+        .add_attribute("escrowed", "false")
+        .add_attribute("winner", ann.as_str());
+    resp.assert_event(&event);
+
+    let resp = app
+        .execute_contract(
+            alex.clone(),
+            contract.addr().clone(),
+            &ExecuteMsg::Retract {
+                receiver: None,
+                bidder: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let event = Event::new("wasm-auction_refund")
+        .add_attribute("auction_id", &auction_id)
+        .add_attribute("bidder", alex.as_str())
+        .add_attribute("beneficiary", alex.as_str())
+        .add_attribute("bid", "9")
+        .add_attribute("bond", "0")
+        .add_attribute("amount", "9")
+        .add_attribute("was_winner", "false");
+    resp.assert_event(&event);
+}
+
+const HOOK_MSGS: Item<Vec<AuctionHookMsg>> = Item::new("hook_msgs");
+
+fn hook_execute(
+    deps: DepsMut,
+    _: Env,
+    _: MessageInfo,
+    msg: AuctionHookExecuteMsg,
+) -> StdResult<Response> {
+    let AuctionHookExecuteMsg::AuctionHook(msg) = msg;
+    let mut msgs = HOOK_MSGS.may_load(deps.storage)?.unwrap_or_default();
+    msgs.push(msg);
+    HOOK_MSGS.save(deps.storage, &msgs)?;
+    Ok(Response::new())
+}
+
+fn hook_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn hook_query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_binary(&HOOK_MSGS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+fn failing_hook_execute(
+    _: DepsMut,
+    _: Env,
+    _: MessageInfo,
+    _: AuctionHookExecuteMsg,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("hook failed"))
+}
+
+#[test]
+fn hooks_notified_on_outbid_and_close() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let hook_code_id = app.store_code(Box::new(ContractWrapper::new(
+        hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let failing_hook_code_id = app.store_code(Box::new(ContractWrapper::new(
+        failing_hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let mut instantiate_hook = |code_id, label| {
+        app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], label, None)
+            .unwrap()
+    };
+    let bot = instantiate_hook(hook_code_id, "bot");
+    let alex_bot = instantiate_hook(hook_code_id, "alex bot");
+    let failing_bot = instantiate_hook(failing_hook_code_id, "failing bot");
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = contract.add_hook(&mut app, &alex, &bot).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract.add_hook(&mut app, &owner, &bot).unwrap();
+    contract.add_hook(&mut app, &owner, &failing_bot).unwrap();
+    assert_eq!(
+        contract.query_hooks(&app).unwrap(),
+        AddressesResponse {
+            addresses: vec![bot.to_string(), failing_bot.to_string()]
+        }
+    );
+    contract
+        .set_bidder_hook(&mut app, &alex, Some(&alex_bot))
+        .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap();
+
+    contract
+        .remove_hook(&mut app, &owner, &failing_bot)
+        .unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    let outbid_alex = AuctionHookMsg::Outbid {
+        previous: alex.to_string(),
+        new: ann.to_string(),
+        amount: coin(12, ATOM),
+    };
+    let outbid_ann = AuctionHookMsg::Outbid {
+        previous: ann.to_string(),
+        new: alex.to_string(),
+        amount: coin(15, ATOM),
+    };
+    let closed = AuctionHookMsg::Closed {
+        winner: Some(alex.to_string()),
+        amount: Some(coin(15, ATOM)),
+    };
+
+    let msgs: Vec<AuctionHookMsg> = app.wrap().query_wasm_smart(&bot, &Empty {}).unwrap();
+    assert_eq!(msgs, vec![outbid_alex.clone(), outbid_ann, closed.clone()]);
+
+    let msgs: Vec<AuctionHookMsg> = app.wrap().query_wasm_smart(&alex_bot, &Empty {}).unwrap();
+    assert_eq!(msgs, vec![outbid_alex, closed]);
+}
+
+#[test]
+fn out_of_gas_hook_cannot_block_bidding() {
+    // multi-test does not meter gas, the hook submessage and its reply are checked directly
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    crate::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    crate::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alex", &[]),
+        ExecuteMsg::SetBidderHook {
+            addr: Some("burner".to_owned()),
+        },
+    )
+    .unwrap();
+    crate::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alex", &coins(10, ATOM)),
+        ExecuteMsg::Bid {},
+    )
+    .unwrap();
+
+    let resp = crate::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ann", &coins(12, ATOM)),
+        ExecuteMsg::Bid {},
+    )
+    .unwrap();
+    let hook = resp
+        .messages
+        .iter()
+        .find(|msg| {
+            matches!(
+                &msg.msg,
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == "burner"
+            )
+        })
+        .unwrap();
+    assert_eq!(hook.gas_limit, Some(HOOK_GAS_LIMIT));
+    assert_eq!(hook.reply_on, ReplyOn::Error);
+
+    // running out of the limit fails only the hook, which is reported on reply
+    let resp = crate::reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: hook.id,
+            result: SubMsgResult::Err("out of gas".to_owned()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.attributes,
+        vec![attr("action", "hook_failed"), attr("error", "out of gas")]
+    );
+}
+
+#[test]
+fn proxy_bids_resolved_against_competing_bids() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(50, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            min_increment: Some(Uint128::new(2)),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.proxy_bid(&mut app, &alex, coin(20, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(alex.as_str(), coin(2, ATOM))
+    );
+    let total_bid = contract.query_total_bid(&app, &alex).unwrap();
+    assert_eq!(total_bid.amount, Some(coin(2, ATOM)));
+    assert_eq!(total_bid.proxy_max, Some(coin(20, ATOM)));
+
+    // alex defends up to the maximum
+    contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(alex.as_str(), coin(12, ATOM))
+    );
+
+    contract.proxy_bid(&mut app, &carl, coin(19, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(alex.as_str(), coin(20, ATOM))
+    );
+    let total_bid = contract.query_total_bid(&app, &carl).unwrap();
+    assert_eq!(total_bid.amount, Some(coin(19, ATOM)));
+    assert_eq!(total_bid.proxy_max, None);
+
+    // the maximum of alex is exceeded
+    contract.bid(&mut app, &ann, coin(15, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(ann.as_str(), coin(25, ATOM))
+    );
+    assert_eq!(
+        contract.query_total_bid(&app, &alex).unwrap().proxy_max,
+        None
+    );
+
+    contract.proxy_bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(alex.as_str(), coin(27, ATOM))
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(27, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(23, ATOM)
+    );
+
+    contract.retract(&mut app, &ann, None).unwrap();
+    contract.retract(&mut app, &carl, None).unwrap();
+    for bidder in [&ann, &carl] {
+        assert_eq!(
+            app.wrap().query_balance(bidder, ATOM).unwrap(),
+            coin(50, ATOM)
+        );
+    }
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+}
+
+#[test]
+fn proxy_bid_pushed_to_maximum_closes() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(50, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    contract.proxy_bid(&mut app, &alex, coin(20, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(19, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(alex.as_str(), coin(20, ATOM))
+    );
+    assert_eq!(
+        contract.query_total_bid(&app, &alex).unwrap().proxy_max,
+        None
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(20, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(30, ATOM)
+    );
+
+    contract.retract(&mut app, &ann, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+}
+
+#[test]
+fn bid_for_credits_beneficiary() {
+    let owner = Addr::unchecked("owner");
+    let custodian = Addr::unchecked("custodian");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &custodian, coins(50, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            eligibility: BidderEligibility::Allowlist,
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+    contract
+        .allow_bidders(&mut app, &owner, &[&alex, &ann])
+        .unwrap();
+
+    let err = contract
+        .bid_for(&mut app, &custodian, &owner, coin(10, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::OwnerCannotBid);
+
+    // the custodian itself is not allowed, but bids for allowed bidders
+    let err = contract
+        .bid(&mut app, &custodian, coin(10, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::BidderNotAllowed);
+
+    contract
+        .bid_for(&mut app, &custodian, &alex, coin(10, ATOM))
+        .unwrap();
+    contract
+        .bid_for(&mut app, &custodian, &ann, coin(12, ATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(ann.as_str(), coin(12, ATOM))
+    );
+    assert_eq!(
+        contract.query_total_bid(&app, &custodian).unwrap().amount,
+        None
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(ann.to_string())
+    );
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(10, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&custodian, ATOM).unwrap(),
+        coin(28, ATOM)
+    );
+}
+
+#[test]
+fn operator_bids_and_retracts_within_approval() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let bot = Addr::unchecked("bot");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &bot, coins(50, ATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &ann, coins(50, ATOM))
+            .unwrap();
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    let expires_at = app.block_info().time.plus_seconds(100);
+    contract
+        .approve_operator(&mut app, &alex, &bot, Some(15), Some(expires_at))
+        .unwrap();
+
+    contract
+        .bid_for(&mut app, &bot, &alex, coin(10, ATOM))
+        .unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    let err = contract
+        .bid_for(&mut app, &bot, &alex, coin(6, ATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SpendLimitExceeded {
+            remaining: coin(5, ATOM)
+        }
+    );
+    contract
+        .bid_for(&mut app, &bot, &alex, coin(5, ATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_operators(&app, &alex).unwrap().operators,
+        vec![OperatorResponse {
+            operator: bot.to_string(),
+            spend_limit: Some(coin(15, ATOM)),
+            spent: coin(15, ATOM),
+            expires_at: Some(expires_at),
+        }]
+    );
+
+    contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap();
+
+    let err = contract
+        .retract_for(&mut app, &ann, &alex, Some(ann.to_string()))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOperator);
+
+    contract
+        .retract_for(&mut app, &bot, &alex, Some(bot.to_string()))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&bot, ATOM).unwrap(),
+        coin(50, ATOM)
+    );
+    assert_eq!(contract.query_total_bid(&app, &alex).unwrap().amount, None);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let err = contract
+        .retract_for(&mut app, &bot, &alex, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovalExpired);
+
+    contract.revoke_operator(&mut app, &alex, &bot).unwrap();
+    assert_eq!(
+        contract.query_operators(&app, &alex).unwrap().operators,
+        vec![]
+    );
+
+    // without an approval only one-off delegated bids remain, the bot cannot manage them
+    contract
+        .bid_for(&mut app, &bot, &alex, coin(30, ATOM))
+        .unwrap();
+    let err = contract
+        .retract_for(&mut app, &bot, &alex, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOperator);
+}
+
+#[test]
+fn rejected_bid_refunded_and_next_bid_leads() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = contract.reject_bid(&mut app, &owner, &alex).unwrap_err();
+    assert_eq!(err, ContractError::NoBid);
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &carl, coin(11, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    let err = contract.reject_bid(&mut app, &alex, &ann).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract.reject_bid(&mut app, &owner, &ann).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(25, ATOM)
+    );
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(carl.as_str(), coin(11, ATOM))
+    );
+
+    // rejecting a bid that is not the highest keeps the leader
+    contract.reject_bid(&mut app, &owner, &alex).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(carl.as_str(), coin(11, ATOM))
+    );
+
+    contract.reject_bid(&mut app, &owner, &carl).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default()
+    );
+
+    contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(alex.to_string())
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(5, ATOM)
+    );
+}
+
+#[test]
+fn highest_bid_at_height() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+    let instantiated_at = app.block_info().height;
+
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    let alex_leads_at = app.block_info().height;
+
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap();
+    let alex_raised_at = app.block_info().height;
+
+    app.update_block(|block| block.height += 5);
+    contract.close(&mut app, &owner).unwrap();
+    let closed_at = app.block_info().height;
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(
+        contract
+            .query_highest_bid_at_height(&app, instantiated_at)
+            .unwrap(),
+        HighestBidResponse::default()
+    );
+    assert_eq!(
+        contract
+            .query_highest_bid_at_height(&app, alex_leads_at)
+            .unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(10, ATOM))
+    );
+    // only the state at the end of the block is kept
+    assert_eq!(
+        contract
+            .query_highest_bid_at_height(&app, alex_raised_at)
+            .unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(15, ATOM))
+    );
+    assert_eq!(
+        contract
+            .query_highest_bid_at_height(&app, closed_at - 1)
+            .unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(15, ATOM))
+    );
+
+    let at_close = contract
+        .query_highest_bid_at_height(&app, closed_at)
+        .unwrap();
+    assert_eq!(at_close, contract.query_highest_bid(&app).unwrap());
+    assert!(at_close.bid_closed);
+    assert_eq!(at_close.winner, Some(alex.to_string()));
+}
+
+#[test]
+fn leaderboard_ranks_standing_bids() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+    let dave = Addr::unchecked("dave");
+    let eve = Addr::unchecked("eve");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl, &dave, &eve] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &carl, coin(11, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    contract.proxy_bid(&mut app, &dave, coin(20, ATOM)).unwrap();
+    // dave defends up to the proxy maximum, both stand at 20
+    contract.bid(&mut app, &eve, coin(20, ATOM)).unwrap();
+
+    let ranked = |rank, bidder: &Addr, amount| RankedBidResponse {
+        rank,
+        bidder: bidder.to_string(),
+        amount: coin(amount, ATOM),
+    };
+
+    let first_page = contract
+        .query_leaderboard(&app, None, Some(3), Some(&carl))
+        .unwrap();
+    let (top, rest) = first_page.bids.split_at(2);
+    assert_eq!(top[0].rank, 1);
+    assert_eq!(top[0].amount, coin(20, ATOM));
+    assert_eq!(top[1].rank, 1);
+    assert_eq!(top[1].amount, coin(20, ATOM));
+    assert_eq!(rest, [ranked(3, &ann, 12)]);
+    assert_eq!(first_page.caller, Some(ranked(4, &carl, 11)));
+
+    let second_page = contract
+        .query_leaderboard(&app, Some(&rest[0]), None, None)
+        .unwrap();
+    assert_eq!(
+        second_page,
+        LeaderboardResponse {
+            bids: vec![ranked(4, &carl, 11), ranked(5, &alex, 10)],
+            caller: None,
+        }
+    );
+
+    // a page may start within equal bids
+    let page = contract
+        .query_leaderboard(&app, Some(&top[0]), Some(2), None)
+        .unwrap();
+    assert_eq!(page.bids, [top[1].clone(), ranked(3, &ann, 12)]);
+
+    // the cursor bidder may have left meanwhile
+    contract.retract(&mut app, &ann, None).unwrap();
+    let page = contract
+        .query_leaderboard(&app, Some(&rest[0]), None, None)
+        .unwrap();
+    assert_eq!(page.bids, [ranked(3, &carl, 11), ranked(4, &alex, 10)]);
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    let leaderboard = contract
+        .query_leaderboard(&app, Some(&page.bids[0]), None, Some(&alex))
+        .unwrap();
+    assert_eq!(leaderboard, LeaderboardResponse::default());
+}
+
+#[test]
+fn equal_bids_resolved_by_tie_break() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(75, ATOM))
+                .unwrap();
+        }
+    });
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    let err = contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidRejected {
+            highest_bid: coin(10, ATOM)
+        }
+    );
+
+    // the earliest of the equal bids leads once the highest is rejected
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            tie_break: TieBreak::EarliestBid,
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        contract.query_config(&app).unwrap().tie_break,
+        TieBreak::EarliestBid
+    );
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(10, ATOM))
+    );
+
+    contract.bid(&mut app, &carl, coin(12, ATOM)).unwrap();
+    contract.reject_bid(&mut app, &owner, &carl).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(10, ATOM))
+    );
+
+    // a bidder who started earlier takes the lead by matching the highest bid
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            tie_break: TieBreak::EarliestFirstBid,
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &alex, coin(2, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(12, ATOM))
+    );
+
+    contract.bid(&mut app, &carl, coin(12, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(12, ATOM))
+    );
+
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(alex.to_string())
+    );
+
+    // a retracted bid does not keep its place once the bidder bids again
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            tie_break: TieBreak::EarliestFirstBid,
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &ann, coin(10, ATOM)).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &alex, coin(12, ATOM)).unwrap();
+    contract.retract(&mut app, &ann, None).unwrap();
+    app.update_block(|block| block.height += 1);
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&alex, coin(12, ATOM))
+    );
+}
+
+#[test]
+fn batch_epochs_cleared_by_crank() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann, &carl] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let err = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            batch_epoch_blocks: Some(0),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBatchEpoch);
+
+    let continuous = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+    let err = continuous.crank(&mut app, &alex).unwrap_err();
+    assert_eq!(err, ContractError::NotBatchAuction);
+
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            batch_epoch_blocks: Some(10),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        contract.query_config(&app).unwrap().batch_epoch_blocks,
+        Some(10)
+    );
+
+    // bids of the running epoch do not lead yet
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+    let err = contract
+        .proxy_bid(&mut app, &carl, coin(20, ATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ProxyBidInBatch);
+
+    contract.crank(&mut app, &carl).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default()
+    );
+    contract.query_epoch(&app, 0).unwrap_err();
+
+    app.update_block(|block| block.height += 10);
+    contract.bid(&mut app, &carl, coin(15, ATOM)).unwrap();
+    contract.crank(&mut app, &alex).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&ann, coin(12, ATOM))
+    );
+    assert_eq!(
+        contract.query_epoch(&app, 0).unwrap(),
+        EpochResponse {
+            epoch: 0,
+            highest_bidder: Some(ann.to_string()),
+            highest_bid: Some(coin(12, ATOM)),
+            ranking: vec![
+                RankedBidResponse {
+                    rank: 1,
+                    bidder: ann.to_string(),
+                    amount: coin(12, ATOM),
+                },
+                RankedBidResponse {
+                    rank: 2,
+                    bidder: alex.to_string(),
+                    amount: coin(10, ATOM),
+                },
+            ],
+        }
+    );
+
+    // pending bids are neither ranked nor promoted on reject, the leader keeps its cleared bid
+    contract.bid(&mut app, &ann, coin(1, ATOM)).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        HighestBidResponse::default().with_bidder(&ann, coin(12, ATOM))
+    );
+    let leaderboard = LeaderboardResponse {
+        bids: vec![
+            RankedBidResponse {
+                rank: 1,
+                bidder: ann.to_string(),
+                amount: coin(12, ATOM),
+            },
+            RankedBidResponse {
+                rank: 2,
+                bidder: alex.to_string(),
+                amount: coin(10, ATOM),
+            },
+        ],
+        caller: None,
+    };
+    assert_eq!(
+        contract
+            .query_leaderboard(&app, None, None, Some(&carl))
+            .unwrap(),
+        leaderboard
+    );
+    assert_eq!(
+        contract
+            .query_leaderboard(&app, None, None, Some(&ann))
+            .unwrap()
+            .caller,
+        Some(leaderboard.bids[0].clone())
+    );
+    let err = contract.reject_bid(&mut app, &owner, &ann).unwrap_err();
+    assert_eq!(err, ContractError::EpochPending);
+
+    // close clears the running epoch as well
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(carl.to_string())
+    );
+    assert_eq!(
+        contract.query_epoch(&app, 1).unwrap(),
+        EpochResponse {
+            epoch: 1,
+            highest_bidder: Some(carl.to_string()),
+            highest_bid: Some(coin(15, ATOM)),
+            ranking: vec![
+                RankedBidResponse {
+                    rank: 1,
+                    bidder: carl.to_string(),
+                    amount: coin(15, ATOM),
+                },
+                RankedBidResponse {
+                    rank: 2,
+                    bidder: ann.to_string(),
+                    amount: coin(13, ATOM),
+                },
+            ],
+        }
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(15, ATOM)
+    );
+
+    let err = contract.crank(&mut app, &alex).unwrap_err();
+    assert_eq!(err, ContractError::BidClosed);
+}
+
+#[test]
+fn governance_sudo_controls_auction() {
+    let owner = Addr::unchecked("owner");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+    let carl = Addr::unchecked("carl");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(50, ATOM))
+                .unwrap();
+        }
+    });
+    let code_id = BiddingContract::store_code(&mut app);
+
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    contract.sudo(&mut app, &SudoMsg::ForceClose {}).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(ann.to_string())
+    );
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(12, ATOM)
+    );
+    let err = contract
+        .sudo(&mut app, &SudoMsg::ForceClose {})
+        .unwrap_err();
+    assert_eq!(err, ContractError::BidClosed);
+
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &instantiate_msg(),
+    )
+    .unwrap();
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    let err = contract
+        .sudo(
+            &mut app,
+            &SudoMsg::ChangeOwner {
+                owner: alex.to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::OwnerCannotBid);
+
+    contract
+        .sudo(
+            &mut app,
+            &SudoMsg::ChangeOwner {
+                owner: carl.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(contract.query_config(&app).unwrap().owner, carl.to_string());
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: carl.to_string()
+        }
+    );
+
+    // the highest bidder is refunded like everyone else
+    contract
+        .sudo(&mut app, &SudoMsg::ForceCancel { limit: Some(1) })
+        .unwrap();
+    let highest_bid = contract.query_highest_bid(&app).unwrap();
+    assert!(highest_bid.bid_closed);
+    assert_eq!(highest_bid.winner, None);
+    assert_eq!(
+        app.wrap().query_balance(&alex, ATOM).unwrap(),
+        coin(40, ATOM)
+    );
+
+    contract.refund_batch(&mut app, &carl, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&ann, ATOM).unwrap(),
+        coin(38, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(contract.addr(), ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&carl, ATOM).unwrap(),
+        coin(0, ATOM)
+    );
+}
+
+#[test]
+fn paused_auction_rejects_bids_and_scoped_actions() {
+    let owner = Addr::unchecked("owner");
+    let guardian = Addr::unchecked("guardian");
+    let alex = Addr::unchecked("alex");
+    let ann = Addr::unchecked("ann");
+
+    let mut app = App::new(|router, _api, storage| {
+        for bidder in [&alex, &ann] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(25, ATOM))
+                .unwrap();
+        }
+    });
+    let code_id = BiddingContract::store_code(&mut app);
+    let contract = BiddingContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        BIDDING_CONTRACT,
+        &InstantiateMsg {
+            guardian: Some(guardian.to_string()),
+            claim_window: Some(1000),
+            ..instantiate_msg()
+        },
+    )
+    .unwrap();
+
+    contract.bid(&mut app, &alex, coin(10, ATOM)).unwrap();
+    contract.bid(&mut app, &ann, coin(12, ATOM)).unwrap();
+
+    let err = contract
+        .pause(&mut app, &alex, PauseScope::default())
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotPauser);
+
+    contract
+        .pause(&mut app, &guardian, PauseScope::default())
+        .unwrap();
+    let config = contract.query_config(&app).unwrap();
+    assert_eq!(config.guardian, Some(guardian.to_string()));
+    assert_eq!(config.paused, Some(PauseScope::default()));
+    let err = contract.bid(&mut app, &alex, coin(5, ATOM)).unwrap_err();
+    assert_eq!(err, ContractError::Paused);
+    assert_eq!(
+        contract
+            .query_simulate_bid(&app, &alex, 5)
+            .unwrap()
+            .rejection,
+        Some(ContractError::Paused.to_string())
+    );
+
+    contract
+        .pause(
+            &mut app,
+            &guardian,
+            PauseScope {
+                close: true,
+                retract: true,
+            },
+        )
+        .unwrap();
+    let err = contract.retract(&mut app, &alex, None).unwrap_err();
+    assert_eq!(err, ContractError::Paused);
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::Paused);
+
+    // the guardian cannot narrow the scope, which would partly unpause
+    contract
+        .pause(&mut app, &guardian, PauseScope::default())
+        .unwrap();
+    assert_eq!(
+        contract.query_config(&app).unwrap().paused,
+        Some(PauseScope {
+            close: true,
+            retract: true,
+        })
+    );
+    let err = contract.retract(&mut app, &alex, None).unwrap_err();
+    assert_eq!(err, ContractError::Paused);
+
+    let err = contract.unpause(&mut app, &guardian).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract.unpause(&mut app, &owner).unwrap();
+    assert_eq!(contract.query_config(&app).unwrap().paused, None);
+
+    contract.retract(&mut app, &alex, None).unwrap();
+    contract.bid(&mut app, &alex, coin(15, ATOM)).unwrap();
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap().winner,
+        Some(alex.to_string())
+    );
+
+    // unclaimed bids cannot be swept while retracting them is paused
+    contract
+        .pause(
+            &mut app,
+            &guardian,
+            PauseScope {
+                close: false,
+                retract: true,
+            },
+        )
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(1001));
+    let err = contract.sweep(&mut app, &owner, None).unwrap_err();
+    assert_eq!(err, ContractError::Paused);
+
+    contract.unpause(&mut app, &owner).unwrap();
+    contract.sweep(&mut app, &owner, None).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, ATOM).unwrap(),
+        coin(27, ATOM)
+    );
+}
@@ -1,11 +1,94 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, Strategy,
+};
 
+pub use crate::msg::{BidderEligibility, CommissionParams, EscrowStatus, PauseScope, TieBreak};
+
+#[cw_serde]
+pub struct Dispute {
+    pub opened_by: Addr,
+    pub reason: String,
+    pub opened_at: Timestamp,
+    pub seller_amount: Option<Uint128>,
+    pub buyer_amount: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct OperatorApproval {
+    pub spend_limit: Option<Uint128>,
+    pub spent: Uint128,
+    pub expires_at: Option<Timestamp>,
+}
+
+impl OperatorApproval {
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+pub struct BidIndexes<'a> {
+    /// Bids ordered by amount, to find the next highest bid.
+    pub amount: MultiIndex<'a, u128, Uint128, Addr>,
+}
+
+impl<'a> IndexList<Uint128> for BidIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Uint128>> + '_> {
+        let v: Vec<&dyn Index<Uint128>> = vec![&self.amount];
+        Box::new(v.into_iter())
+    }
+}
+
+fn bid_amount_idx(_pk: &[u8], amount: &Uint128) -> u128 {
+    amount.u128()
+}
+
+/// Block height and transaction index of the first and the latest bid of a bidder.
 #[cw_serde]
-pub struct CommissionParams {
-    pub part: Decimal,
-    pub minimum_tokens: Uint128,
+pub struct BidOrder {
+    pub first: (u64, u32),
+    pub last: (u64, u32),
+}
+
+impl BidOrder {
+    /// Position a tied bid is ranked by, the earlier one leads.
+    pub fn tie_position(&self, tie_break: &TieBreak) -> (u64, u32) {
+        match tie_break {
+            TieBreak::EarliestFirstBid => self.first,
+            TieBreak::Reject | TieBreak::EarliestBid => self.last,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct BatchEpochs {
+    pub blocks: u64,
+    pub start_height: u64,
+}
+
+impl BatchEpochs {
+    pub fn epoch(&self, height: u64) -> u64 {
+        height.saturating_sub(self.start_height) / self.blocks
+    }
+}
+
+#[cw_serde]
+pub struct EpochResult {
+    pub highest_bidder: Option<Addr>,
+    pub highest_bid: Uint128,
+    pub ranking: Vec<(Addr, Uint128)>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AuctionStats {
+    pub bidders: u64,
+    pub bids: u64,
+    pub refunded: Uint128,
+    pub paid_to_seller: Uint128,
+    pub first_bid_height: Option<u64>,
+    pub last_bid_height: Option<u64>,
 }
 
 pub const ITEM: Item<String> = Item::new("item");
@@ -13,6 +96,64 @@ pub const BID_DENOM: Item<String> = Item::new("bid_denom");
 pub const OWNER: Item<Addr> = Item::new("owner");
 pub const COMMISSION_PARAMS: Item<CommissionParams> = Item::new("commission");
 pub const BID_OPEN: Item<bool> = Item::new("bid_open");
-pub const BIDS: Map<Addr, Uint128> = Map::new("bids");
-pub const HIGHEST_BID: Item<Uint128> = Item::new("highest_bid");
-pub const HIGHEST_BIDDER: Item<Addr> = Item::new("highest_bidder");
+pub const BIDS: IndexedMap<Addr, Uint128, BidIndexes> = IndexedMap::new(
+    "bids",
+    BidIndexes {
+        amount: MultiIndex::new(bid_amount_idx, "bids", "bids__amount"),
+    },
+);
+pub const HIGHEST_BID: SnapshotItem<Uint128> = SnapshotItem::new(
+    "highest_bid",
+    "highest_bid__checkpoints",
+    "highest_bid__changelog",
+    Strategy::EveryBlock,
+);
+pub const HIGHEST_BIDDER: SnapshotItem<Addr> = SnapshotItem::new(
+    "highest_bidder",
+    "highest_bidder__checkpoints",
+    "highest_bidder__changelog",
+    Strategy::EveryBlock,
+);
+pub const ELIGIBILITY: Item<BidderEligibility> = Item::new("eligibility");
+pub const ALLOWLIST: Map<Addr, Empty> = Map::new("allowlist");
+pub const BLOCKLIST: Map<Addr, Empty> = Map::new("blocklist");
+pub const BOND_AMOUNT: Item<Uint128> = Item::new("bond_amount");
+pub const BONDS: Map<Addr, Uint128> = Map::new("bonds");
+pub const ARBITER: Item<Addr> = Item::new("arbiter");
+pub const ESCROW_STATUS: Item<EscrowStatus> = Item::new("escrow_status");
+pub const ESCROW_TIMEOUT: Item<u64> = Item::new("escrow_timeout");
+pub const ESCROW_RELEASE_AT: Item<Timestamp> = Item::new("escrow_release_at");
+pub const DISPUTE: Item<Dispute> = Item::new("dispute");
+
+pub const TOTAL_BIDS: Item<Uint128> = Item::new("total_bids");
+pub const TOTAL_BONDS: Item<Uint128> = Item::new("total_bonds");
+pub const CLOSED_AT: Item<Timestamp> = Item::new("closed_at");
+pub const CLOSED_HEIGHT: Item<u64> = Item::new("closed_height");
+pub const CLAIM_WINDOW: Item<u64> = Item::new("claim_window");
+pub const TREASURY: Item<Addr> = Item::new("treasury");
+pub const FORFEITED: Map<Addr, Uint128> = Map::new("forfeited");
+pub const HOOKS: Map<Addr, Empty> = Map::new("hooks");
+pub const BIDDER_HOOKS: Map<Addr, Addr> = Map::new("bidder_hooks");
+/// Proxy maximum of the highest bidder, the standing bid is kept in `BIDS`.
+pub const PROXY_BIDS: Map<Addr, Uint128> = Map::new("proxy_bids");
+pub const MIN_INCREMENT: Item<Uint128> = Item::new("min_increment");
+/// Approvals keyed by (bidder, operator).
+pub const OPERATORS: Map<(Addr, Addr), OperatorApproval> = Map::new("operators");
+pub const COMMISSIONS: Map<Addr, Uint128> = Map::new("commissions");
+pub const TOTAL_COMMISSION: Item<Uint128> = Item::new("total_commission");
+pub const STATS: Item<AuctionStats> = Item::new("stats");
+pub const TIE_BREAK: Item<TieBreak> = Item::new("tie_break");
+pub const BID_ORDER: Map<Addr, BidOrder> = Map::new("bid_order");
+pub const BATCH_EPOCHS: Item<BatchEpochs> = Item::new("batch_epochs");
+/// Total bid of every bidder as of its last bid in a not yet cleared epoch.
+pub const EPOCH_BIDS: Map<(u64, Addr), Uint128> = Map::new("epoch_bids");
+pub const EPOCH_RESULTS: Map<u64, EpochResult> = Map::new("epoch_results");
+/// Total bid of every bidder as of its last cleared epoch, what batch auctions rank.
+pub const CLEARED_BIDS: IndexedMap<Addr, Uint128, BidIndexes> = IndexedMap::new(
+    "cleared_bids",
+    BidIndexes {
+        amount: MultiIndex::new(bid_amount_idx, "cleared_bids", "cleared_bids__amount"),
+    },
+);
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+pub const PAUSED: Item<PauseScope> = Item::new("paused");